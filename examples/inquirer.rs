@@ -25,11 +25,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     form.run(prompt::Confirm::new("Confirm this").default(false))?;
 
     prompt::confirm("Confirm this?")?;
-    prompt::Input::new("name")
-        //.default("Rasmus")
-        .validate(valid::MinLen(6))
-        .validate(valid::Parse::<f64>::new())
-        .required()
+    prompt::Number::<f64>::new("number")
+        .min(0.0)
+        .step(0.5)
         .build()
         .run()?;
 
@@ -39,7 +37,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .map(|m| format!("Choice {}", m))
         .collect::<Vec<_>>();
 
-    let select = prompt::select("One choice:", &choices)?;
+    let select = prompt::Select::new("One choice:", &choices)
+        .filter(true)
+        .build()
+        .run()?;
 
     prompt::MultiSelect::new("Multiple choices:", &choices)
         .validate(valid::MinLen(4))
@@ -0,0 +1,321 @@
+//! `#[derive(Prompt)]` for the `prompt` crate.
+//!
+//! On structs with named fields every field becomes a question:
+//!
+//! * `String` is asked with `Input`, or `Password` with `#[prompt(password)]`
+//! * `bool` is asked with `Confirm`
+//! * numbers are asked with `Input` parsing the answer
+//! * `Vec<T>` is asked with `MultiSelect` over the variants of `T`
+//! * any other type is asked with `Select` over its variants
+//!
+//! `Option<T>` fields are rejected, there is no prompt that can be left
+//! unanswered.
+//!
+//! On enums with unit variants it implements `Choice` and `Variants`, so the
+//! enum can be used as a field type.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident,
+    Lit, LitStr, Meta, NestedMeta, PathArguments, Result, Type,
+};
+
+#[proc_macro_derive(Prompt, attributes(prompt))]
+pub fn derive_prompt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let expanded = match &input.data {
+        Data::Struct(_) => derive_struct(&input),
+        Data::Enum(_) => derive_enum(&input),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input.ident,
+            "Prompt cannot be derived for unions",
+        )),
+    };
+    expanded.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    message: Option<LitStr>,
+    name: Option<LitStr>,
+    default: Option<Lit>,
+    validate: Vec<Expr>,
+    password: bool,
+}
+
+enum Kind {
+    Text,
+    Bool,
+    Number,
+    Many(Box<Type>),
+    One,
+}
+
+fn derive_struct(input: &DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "Prompt can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => unreachable!(),
+    };
+
+    let mut names = Vec::new();
+    let mut asks = Vec::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().unwrap();
+        let attrs = field_attrs(&field.attrs)?;
+        names.push(field_ident);
+        asks.push(ask(field_ident, &field.ty, attrs)?);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::prompt::Prompt for #ident #ty_generics #where_clause {
+            fn prompt<R: ::std::io::Read, W: ::prompt::Terminal>(
+                __form: &mut ::prompt::Form<R, W>,
+            ) -> ::prompt::error::Result<Self> {
+                #(let #names = #asks;)*
+                Ok(#ident { #(#names),* })
+            }
+        }
+    })
+}
+
+fn ask(ident: &Ident, ty: &Type, attrs: FieldAttrs) -> Result<TokenStream2> {
+    let field = ident.to_string();
+    let name = attrs
+        .name
+        .unwrap_or_else(|| LitStr::new(&field, ident.span()));
+    let message = attrs
+        .message
+        .unwrap_or_else(|| LitStr::new(&humanize(&field), ident.span()));
+
+    let kind = kind(ty)?;
+    if !attrs.validate.is_empty() && !matches!(kind, Kind::Text | Kind::Number) {
+        return Err(Error::new_spanned(
+            ident,
+            "`validate` is only supported on text and number fields",
+        ));
+    }
+    if attrs.password && !matches!(kind, Kind::Text) {
+        return Err(Error::new_spanned(
+            ident,
+            "`password` is only supported on String fields",
+        ));
+    }
+    if attrs.default.is_some() && matches!(kind, Kind::Many(_) | Kind::One) {
+        return Err(Error::new_spanned(
+            ident,
+            "`default` is not supported on select fields",
+        ));
+    }
+
+    let validate = &attrs.validate;
+    Ok(match kind {
+        Kind::Text if attrs.password => quote! {
+            __form.run(::prompt::Password::new(#message).name(#name))?
+        },
+        Kind::Text => {
+            let default = attrs.default.map(|d| quote!(.default(#d)));
+            quote! {
+                __form.run(
+                    ::prompt::Input::new(#message)
+                        .name(#name)
+                        #default
+                        #(.validate(#validate))*
+                )?
+            }
+        }
+        Kind::Bool => {
+            let default = attrs.default.map(|d| quote!(.default(#d)));
+            quote! {
+                __form.run(::prompt::Confirm::new(#message).name(#name) #default)?
+            }
+        }
+        Kind::Number => {
+            let default = attrs.default.map(|d| {
+                let d = match d {
+                    Lit::Str(s) => s.value(),
+                    Lit::Int(i) => i.base10_digits().to_string(),
+                    Lit::Float(f) => f.base10_digits().to_string(),
+                    other => return Err(Error::new_spanned(other, "expected a number")),
+                };
+                Ok(quote!(.default(#d)))
+            });
+            let default = default.transpose()?;
+            quote! {
+                __form.run(
+                    ::prompt::Input::new(#message)
+                        .name(#name)
+                        #default
+                        #(.validate(#validate))*
+                        .parse::<#ty>()
+                )?
+            }
+        }
+        Kind::Many(inner) => quote! {
+            ::prompt::__private::multi_select::<#inner, R, W>(__form, #message, #name)?
+        },
+        Kind::One => quote! {
+            ::prompt::__private::select::<#ty, R, W>(__form, #message, #name)?
+        },
+    })
+}
+
+fn kind(ty: &Type) -> Result<Kind> {
+    let path = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return Ok(Kind::One),
+    };
+    let last = match path.segments.last() {
+        Some(last) => last,
+        None => return Ok(Kind::One),
+    };
+
+    Ok(match last.ident.to_string().as_str() {
+        "String" => Kind::Text,
+        "bool" => Kind::Bool,
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" | "f32" | "f64" => Kind::Number,
+        "Vec" => match &last.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(GenericArgument::Type(inner)) => Kind::Many(Box::new(inner.clone())),
+                _ => Kind::One,
+            },
+            _ => Kind::One,
+        },
+        "Option" => {
+            return Err(Error::new_spanned(
+                ty,
+                "Prompt cannot ask for `Option` fields, use the inner type",
+            ))
+        }
+        _ => Kind::One,
+    })
+}
+
+fn field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut out = FieldAttrs::default();
+    for meta in prompt_metas(attrs)? {
+        match &meta {
+            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("password") => out.password = true,
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("message") => {
+                out.message = Some(lit_str(&nv.lit)?)
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                out.name = Some(lit_str(&nv.lit)?)
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("default") => {
+                out.default = Some(nv.lit.clone())
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("validate") => {
+                out.validate.push(lit_str(&nv.lit)?.parse()?)
+            }
+            _ => return Err(Error::new_spanned(meta, "unknown prompt attribute")),
+        }
+    }
+    Ok(out)
+}
+
+fn prompt_metas(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("prompt")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            other => return Err(Error::new_spanned(other, "expected #[prompt(...)]")),
+        }
+    }
+    Ok(metas)
+}
+
+fn lit_str(lit: &Lit) -> Result<LitStr> {
+    match lit {
+        Lit::Str(s) => Ok(s.clone()),
+        _ => Err(Error::new_spanned(lit, "expected a string")),
+    }
+}
+
+fn derive_enum(input: &DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => unreachable!(),
+    };
+
+    let mut idents = Vec::new();
+    let mut texts = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "Prompt can only be derived for enums with unit variants",
+            ));
+        }
+
+        let mut text = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+        for meta in prompt_metas(&variant.attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("text") => {
+                    text = lit_str(&nv.lit)?
+                }
+                _ => return Err(Error::new_spanned(meta, "unknown prompt attribute")),
+            }
+        }
+        idents.push(&variant.ident);
+        texts.push(text);
+    }
+
+    if idents.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "Prompt cannot be derived for enums without variants",
+        ));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::prompt::Choice for #ident #ty_generics #where_clause {
+            type Text = &'static str;
+            type Value = Self;
+
+            fn text(&self) -> &Self::Text {
+                match self {
+                    #(#ident::#idents => &#texts,)*
+                }
+            }
+
+            fn value(&self) -> &Self::Value {
+                self
+            }
+        }
+
+        impl #impl_generics ::prompt::Variants for #ident #ty_generics #where_clause {
+            fn variants() -> Vec<Self> {
+                vec![#(#ident::#idents),*]
+            }
+        }
+    })
+}
+
+/// `first_name` -> `First name`
+fn humanize(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    for (i, c) in field.trim_start_matches("r#").chars().enumerate() {
+        match c {
+            '_' => out.push(' '),
+            c if i == 0 => out.extend(c.to_uppercase()),
+            c => out.push(c),
+        }
+    }
+    out
+}
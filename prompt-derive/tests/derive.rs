@@ -0,0 +1,115 @@
+use prompt::error::Error;
+use prompt::testing::{Script, VirtualInput, VirtualTerminal};
+use prompt::{Choice, Form, Prompt, Theme, Variants};
+use std::collections::HashMap;
+use termion::event::Key;
+
+#[derive(Debug, PartialEq, Prompt)]
+enum Size {
+    Small,
+    #[prompt(text = "Medium size")]
+    Medium,
+    Large,
+}
+
+#[derive(Debug, PartialEq, Prompt)]
+enum Topping {
+    Cheese,
+    Ham,
+    Pineapple,
+}
+
+#[derive(Debug, PartialEq, Prompt)]
+struct Order {
+    #[prompt(message = "Your name", validate = "prompt::valid::MinLen(2)")]
+    name: String,
+    #[prompt(password)]
+    secret: String,
+    #[prompt(default = 2)]
+    pizza_count: u32,
+    delivery: bool,
+    size: Size,
+    toppings: Vec<Topping>,
+}
+
+fn session(script: Script) -> (Form<VirtualInput, VirtualTerminal>, VirtualTerminal) {
+    let term = VirtualTerminal::new(40, 12);
+    let form = Form::new(term.input(script), term.clone(), Theme::new());
+    (form, term)
+}
+
+#[test]
+fn enum_choices() {
+    assert_eq!(
+        Size::variants(),
+        vec![Size::Small, Size::Medium, Size::Large]
+    );
+    assert_eq!(*Size::Medium.text(), "Medium size");
+    assert_eq!(Size::Large.value(), &Size::Large);
+}
+
+#[test]
+fn asks_every_field() {
+    let script = Script::new()
+        .text("Rasmus")
+        .enter()
+        .text("hunter2")
+        .enter()
+        .enter()
+        .text("n")
+        .key(Key::Down)
+        .enter()
+        .text(" ")
+        .key(Key::Down)
+        .key(Key::Down)
+        .text(" ")
+        .enter();
+    let (mut form, term) = session(script);
+    let order: Order = form.prompt().unwrap();
+
+    assert_eq!(
+        order,
+        Order {
+            name: "Rasmus".to_string(),
+            secret: "hunter2".to_string(),
+            pizza_count: 2,
+            delivery: false,
+            size: Size::Medium,
+            toppings: vec![Topping::Cheese, Topping::Pineapple],
+        }
+    );
+    assert!(term.contents().contains("? Your name Rasmus"));
+    assert!(term.contents().contains("? Pizza count 2"));
+}
+
+#[test]
+fn fields_are_named() {
+    let mut answers = HashMap::new();
+    answers.insert("name", "Rasmus");
+    answers.insert("secret", "hunter2");
+    answers.insert("pizza_count", "3");
+    answers.insert("delivery", "yes");
+    answers.insert("size", "Large");
+    answers.insert("toppings", "Ham");
+    let (form, _) = session(Script::new());
+    let order: Order = form.answers(answers).prompt().unwrap();
+
+    assert_eq!(order.pizza_count, 3);
+    assert!(order.delivery);
+    assert_eq!(order.size, Size::Large);
+    assert_eq!(order.toppings, vec![Topping::Ham]);
+}
+
+#[test]
+fn validates_numbers() {
+    let mut answers = HashMap::new();
+    answers.insert("name", "Rasmus");
+    answers.insert("secret", "hunter2");
+    answers.insert("pizza_count", "many");
+    let (form, _) = session(Script::new());
+
+    match form.answers(answers).prompt::<Order>() {
+        Err(Error::InvalidAnswer(msg, _)) => assert_eq!(msg, "Pizza count"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
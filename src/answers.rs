@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Answers given up front, looked up by the name of a prompt
+pub trait AnswerSource {
+    fn answer(&self, name: &str) -> Option<String>;
+}
+
+impl AnswerSource for HashMap<String, String> {
+    fn answer(&self, name: &str) -> Option<String> {
+        self.get(name).cloned()
+    }
+}
+
+impl<'a> AnswerSource for HashMap<&'a str, &'a str> {
+    fn answer(&self, name: &str) -> Option<String> {
+        self.get(name).map(|m| m.to_string())
+    }
+}
+
+/// Answers from environment variables
+///
+/// The variable for a prompt is the prefix followed by the name in upper
+/// case, with anything but letters and digits replaced by `_`. With the
+/// prefix `APP_` the prompt `user-name` is answered by `APP_USER_NAME`.
+pub struct EnvAnswers {
+    prefix: String,
+}
+
+impl EnvAnswers {
+    pub fn new(prefix: impl ToString) -> EnvAnswers {
+        EnvAnswers {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    pub fn key(&self, name: &str) -> String {
+        let name = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        format!("{}{}", self.prefix, name)
+    }
+}
+
+impl AnswerSource for EnvAnswers {
+    fn answer(&self, name: &str) -> Option<String> {
+        std::env::var(self.key(name)).ok()
+    }
+}
+
+/// Answers read from a file of `name = value` lines
+///
+/// Empty lines and lines starting with `#` are ignored.
+pub struct FileAnswers {
+    answers: HashMap<String, String>,
+}
+
+impl FileAnswers {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<FileAnswers> {
+        let content = fs::read_to_string(path)?;
+        Ok(FileAnswers::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> FileAnswers {
+        let answers = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.splitn(2, '=');
+                let name = parts.next()?.trim();
+                let value = parts.next()?.trim();
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        FileAnswers { answers }
+    }
+}
+
+impl AnswerSource for FileAnswers {
+    fn answer(&self, name: &str) -> Option<String> {
+        self.answers.answer(name)
+    }
+}
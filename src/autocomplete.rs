@@ -0,0 +1,259 @@
+use super::choice::{Choice, Cursor};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use std::time::Duration;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::{clear, cursor};
+
+/// Choices looked up by an `Autocomplete` for the text typed so far
+pub trait AutocompleteSource {
+    type Item: Choice;
+
+    /// Choices matching `query`, best match first
+    fn search(&self, query: &str) -> Vec<Self::Item>;
+}
+
+impl<F, C> AutocompleteSource for F
+where
+    F: Fn(&str) -> Vec<C>,
+    C: Choice,
+{
+    type Item = C;
+
+    fn search(&self, query: &str) -> Vec<C> {
+        self(query)
+    }
+}
+
+pub struct AutocompleteBuilder<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: S,
+    page_size: usize,
+    debounce: Duration,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: AutocompleteSource> AutocompleteBuilder<'a, S> {
+    pub fn new(msg: &'a str, source: S) -> AutocompleteBuilder<'a, S> {
+        AutocompleteBuilder {
+            msg,
+            name: None,
+            source,
+            page_size: 8,
+            debounce: Duration::from_millis(150),
+            theme: None,
+        }
+    }
+
+    pub fn page_size(mut self, size: usize) -> AutocompleteBuilder<'a, S> {
+        self.page_size = std::cmp::max(size, 1);
+        self
+    }
+
+    /// How long typing has to stop before the source is searched
+    pub fn debounce(mut self, debounce: Duration) -> AutocompleteBuilder<'a, S> {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> AutocompleteBuilder<'a, S> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Autocomplete<'a, S> {
+        Autocomplete {
+            msg: self.msg,
+            name: self.name,
+            source: self.source,
+            page_size: self.page_size,
+            debounce: self.debounce,
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'a, S: AutocompleteSource> IntoEditor for AutocompleteBuilder<'a, S> {
+    type Editor = Autocomplete<'a, S>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Picks a choice searched for as the user types, for sets of choices too
+/// large or too slow to list up front.
+///
+/// The source is searched once typing stops for the debounce time, with a
+/// loading row shown until then.
+pub struct Autocomplete<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: S,
+    page_size: usize,
+    debounce: Duration,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: AutocompleteSource> Autocomplete<'a, S> {
+    pub fn new(msg: &'a str, source: S) -> AutocompleteBuilder<'a, S> {
+        AutocompleteBuilder::new(msg, source)
+    }
+
+    pub fn run(&self) -> Result<S::Item> {
+        <Autocomplete<'a, S> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+}
+
+impl<'a, S: AutocompleteSource> Editor for Autocomplete<'a, S> {
+    type Output = S::Item;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let rows = self.page_size;
+
+        write!(stdout, "{}", cursor::Hide)?;
+        for _ in 0..rows {
+            writeln!(stdout)?;
+        }
+
+        let mut query = String::new();
+        let mut results = Vec::new();
+        // Whether `results` are out of date with `query`
+        let mut stale = true;
+        let mut cursor = Cursor::new(0, rows, 0, |_| true);
+
+        let mut input = stdin.keys();
+
+        let idx = loop {
+            write!(stdout, "{}", cursor::Up(rows as u16))?;
+            theme.print_question(&mut stdout, self.msg, None)?;
+            write!(stdout, "{}", query)?;
+
+            let visible = if stale {
+                0
+            } else {
+                std::cmp::min(results.len(), rows)
+            };
+
+            for (i, choice) in results.iter().skip(cursor.offset).take(visible).enumerate() {
+                write!(stdout, "\n\r{}", clear::CurrentLine)?;
+                theme.print_choice(&mut stdout, choice, cursor.row() == i)?;
+            }
+            if visible == 0 {
+                write!(stdout, "\n\r")?;
+                theme.print_placeholder(
+                    &mut stdout,
+                    if stale { "Loading…" } else { "No matches" },
+                )?;
+            }
+            for _ in std::cmp::max(visible, 1)..rows {
+                write!(stdout, "\n\r{}", clear::CurrentLine)?;
+            }
+
+            stdout.flush()?;
+
+            // Keys typed before the debounce time is up replace the query
+            // without searching for it
+            if stale && !stdout.poll_input(self.debounce)? {
+                results = self.source.search(&query);
+                stale = false;
+                cursor = Cursor::new(results.len(), rows, 0, |_| true);
+                continue;
+            }
+
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            match next {
+                Key::Char('\n') if stale => {
+                    results = self.source.search(&query);
+                    stale = false;
+                    cursor = Cursor::new(results.len(), rows, 0, |_| true);
+                }
+                Key::Char('\n') if visible > 0 => break cursor.pos,
+                Key::Up => cursor.up(|_| true),
+                Key::Down => cursor.down(|_| true),
+                Key::Char(c) if c != '\n' && c != '\t' => {
+                    query.push(c);
+                    stale = true;
+                }
+                Key::Backspace if !query.is_empty() => {
+                    query.pop();
+                    stale = true;
+                }
+                Key::Ctrl('c') => {
+                    write!(stdout, "\n\r{}", cursor::Show)?;
+                    return Err(Error::UserAborted);
+                }
+                _ => {}
+            }
+        };
+
+        for _ in 0..rows {
+            write!(stdout, "{}{}", clear::CurrentLine, cursor::Up(1))?;
+        }
+
+        write!(stdout, "\r{}", cursor::Show)?;
+
+        let choice = results.swap_remove(idx);
+        theme.print_results(&mut stdout, self.msg, &choice.text().to_string())?;
+
+        Ok(choice)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    /// Searches the source for the answer and takes the result with the
+    /// same text
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<S::Item> {
+        let answer = match answer {
+            Some(answer) if !answer.trim().is_empty() => answer.trim(),
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        let mut results = self.source.search(answer);
+        let idx = results
+            .iter()
+            .position(|c| c.text().to_string() == answer)
+            .ok_or_else(|| {
+                Error::InvalidAnswer(self.msg.to_string(), format!("no choice {:?}", answer))
+            })?;
+
+        let choice = results.swap_remove(idx);
+        print_answer(stdout, theme, self.msg, &choice.text().to_string())?;
+        Ok(choice)
+    }
+}
+
+pub fn autocomplete<S: AutocompleteSource>(msg: &str, source: S) -> Result<S::Item> {
+    AutocompleteBuilder::new(msg, source).build().run()
+}
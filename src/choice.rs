@@ -1,3 +1,4 @@
+use super::error::{Error, Result};
 use std::fmt;
 
 /// An option the user can choose
@@ -15,6 +16,17 @@ pub trait Choice {
 
     /// Get a reference to the value of this choice
     fn value(&self) -> &Self::Value;
+
+    /// Why this choice cannot be picked, or `None` when it can
+    fn disabled(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether this is a separator between groups of choices, showing its
+    /// text as a heading
+    fn is_separator(&self) -> bool {
+        false
+    }
 }
 
 impl<'a> Choice for &'a str {
@@ -58,3 +70,240 @@ where
         &self.1
     }
 }
+
+/// Lets a `Select` or `MultiSelect` over borrowed choices return references
+/// to them
+impl<C: Choice> Choice for &C {
+    type Text = C::Text;
+    type Value = C::Value;
+
+    fn text(&self) -> &Self::Text {
+        (**self).text()
+    }
+
+    fn value(&self) -> &Self::Value {
+        (**self).value()
+    }
+
+    fn disabled(&self) -> Option<&str> {
+        (**self).disabled()
+    }
+
+    fn is_separator(&self) -> bool {
+        (**self).is_separator()
+    }
+}
+
+/// A choice with a key picking it in an `Expand` prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyed<C> {
+    key: char,
+    choice: C,
+}
+
+impl<C> Keyed<C> {
+    pub fn new(key: char, choice: C) -> Keyed<C> {
+        Keyed { key, choice }
+    }
+
+    pub fn key(&self) -> char {
+        self.key
+    }
+
+    pub fn choice(&self) -> &C {
+        &self.choice
+    }
+}
+
+impl<C: Choice> Choice for Keyed<C> {
+    type Text = C::Text;
+    type Value = C::Value;
+
+    fn text(&self) -> &Self::Text {
+        self.choice.text()
+    }
+
+    fn value(&self) -> &Self::Value {
+        self.choice.value()
+    }
+
+    fn disabled(&self) -> Option<&str> {
+        self.choice.disabled()
+    }
+
+    fn is_separator(&self) -> bool {
+        self.choice.is_separator()
+    }
+}
+
+/// A row of a `Select` or `MultiSelect`, which is either a choice or a row
+/// that cannot be picked
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry<C> {
+    Enabled(C),
+    /// Shown, but skipped over, with the reason it cannot be picked
+    Disabled(C, String),
+    /// A heading between groups of choices
+    Separator(C),
+}
+
+impl<C> Entry<C> {
+    pub fn disabled(choice: C, reason: impl Into<String>) -> Entry<C> {
+        Entry::Disabled(choice, reason.into())
+    }
+
+    pub fn choice(&self) -> &C {
+        match self {
+            Entry::Enabled(choice) | Entry::Disabled(choice, _) | Entry::Separator(choice) => {
+                choice
+            }
+        }
+    }
+}
+
+impl<C> From<C> for Entry<C> {
+    fn from(choice: C) -> Entry<C> {
+        Entry::Enabled(choice)
+    }
+}
+
+impl<C: Choice> Choice for Entry<C> {
+    type Text = C::Text;
+    type Value = C::Value;
+
+    fn text(&self) -> &Self::Text {
+        self.choice().text()
+    }
+
+    fn value(&self) -> &Self::Value {
+        self.choice().value()
+    }
+
+    fn disabled(&self) -> Option<&str> {
+        match self {
+            Entry::Disabled(_, reason) => Some(reason),
+            Entry::Enabled(choice) | Entry::Separator(choice) => choice.disabled(),
+        }
+    }
+
+    fn is_separator(&self) -> bool {
+        match self {
+            Entry::Separator(_) => true,
+            Entry::Enabled(choice) | Entry::Disabled(choice, _) => choice.is_separator(),
+        }
+    }
+}
+
+/// A choice picked from a list, by where it was and its value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection<T> {
+    pub index: usize,
+    pub value: T,
+}
+
+/// Whether a choice can be picked, as opposed to being disabled or a
+/// separator
+pub(crate) fn is_selectable<C: Choice>(choice: &C) -> bool {
+    choice.disabled().is_none() && !choice.is_separator()
+}
+
+/// The highlighted row of a list shown a page of `rows` at a time, moving
+/// past the rows that cannot be picked
+pub(crate) struct Cursor {
+    /// Highlighted row of the list
+    pub pos: usize,
+    /// First row of the list on the page
+    pub offset: usize,
+    len: usize,
+    rows: usize,
+}
+
+impl Cursor {
+    /// Start on `start`, or the first row after it that can be picked
+    pub fn new(
+        len: usize,
+        rows: usize,
+        start: usize,
+        selectable: impl Fn(usize) -> bool,
+    ) -> Cursor {
+        let pos = (start..len)
+            .chain(0..start)
+            .find(|i| selectable(*i))
+            .unwrap_or(0);
+        Cursor {
+            pos,
+            offset: (pos + 1).saturating_sub(rows),
+            len,
+            rows,
+        }
+    }
+
+    /// Row of the page highlighted
+    pub fn row(&self) -> usize {
+        self.pos - self.offset
+    }
+
+    pub fn up(&mut self, selectable: impl Fn(usize) -> bool) {
+        match (0..self.pos).rev().find(|i| selectable(*i)) {
+            Some(pos) => self.go(pos),
+            // Bring the rows above into view, like a heading
+            None if self.offset > 0 && self.row() + 1 < self.rows => self.offset -= 1,
+            None => {}
+        }
+    }
+
+    pub fn down(&mut self, selectable: impl Fn(usize) -> bool) {
+        match (self.pos + 1..self.len).find(|i| selectable(*i)) {
+            Some(pos) => self.go(pos),
+            None if self.offset + self.rows < self.len && self.row() > 0 => self.offset += 1,
+            None => {}
+        }
+    }
+
+    fn go(&mut self, pos: usize) {
+        self.pos = pos;
+        if pos < self.offset {
+            self.offset = pos;
+        } else if pos >= self.offset + self.rows {
+            self.offset = pos + 1 - self.rows;
+        }
+    }
+}
+
+/// Like `find_choice`, failing for choices that cannot be picked
+pub(crate) fn find_selectable<C: Choice>(msg: &str, choices: &[C], answer: &str) -> Result<usize> {
+    let idx = find_choice(msg, choices, answer)?;
+    let choice = &choices[idx];
+    if let Some(reason) = choice.disabled() {
+        return Err(Error::InvalidAnswer(
+            msg.to_string(),
+            format!("{} is disabled: {}", choice.text(), reason),
+        ));
+    }
+    if choice.is_separator() {
+        return Err(Error::InvalidAnswer(
+            msg.to_string(),
+            format!("no choice {:?}", answer.trim()),
+        ));
+    }
+    Ok(idx)
+}
+
+/// Find the choice an answer refers to, by text or by index
+pub(crate) fn find_choice<C: Choice>(msg: &str, choices: &[C], answer: &str) -> Result<usize> {
+    let answer = answer.trim();
+
+    // Text first, so a choice that reads like a number can be picked by it
+    if let Some(idx) = choices.iter().position(|c| c.text().to_string() == answer) {
+        return Ok(idx);
+    }
+
+    match answer.parse::<usize>() {
+        Ok(idx) if idx >= choices.len() => Err(Error::InvalidChoice(idx)),
+        Ok(idx) => Ok(idx),
+        Err(_) => Err(Error::InvalidAnswer(
+            msg.to_string(),
+            format!("no choice {:?}", answer),
+        )),
+    }
+}
@@ -0,0 +1,144 @@
+use super::choice::Choice;
+use std::fs;
+
+/// Completions for the text typed into an `Input`
+pub trait Completer {
+    /// Candidates replacing `input`, in the order they are offered
+    fn complete(&self, input: &str) -> Vec<String>;
+}
+
+impl<T: Completer + ?Sized> Completer for &T {
+    fn complete(&self, input: &str) -> Vec<String> {
+        (**self).complete(input)
+    }
+}
+
+/// A single suggestion for the text typed into an `Input`, shown after the
+/// cursor and accepted with Right or End
+pub trait Suggester {
+    /// A line starting with `input`, if any
+    fn suggest(&self, input: &str) -> Option<String>;
+}
+
+impl<T: Suggester + ?Sized> Suggester for &T {
+    fn suggest(&self, input: &str) -> Option<String> {
+        (**self).suggest(input)
+    }
+}
+
+/// Completes the choices whose text starts with the input
+impl<C: Choice> Completer for [C] {
+    fn complete(&self, input: &str) -> Vec<String> {
+        self.iter()
+            .map(|choice| choice.text().to_string())
+            .filter(|text| text.starts_with(input))
+            .collect()
+    }
+}
+
+/// Completes words from a fixed list
+#[derive(Debug, Clone, Default)]
+pub struct WordCompleter {
+    words: Vec<String>,
+    ignore_case: bool,
+}
+
+impl WordCompleter {
+    pub fn new<I, S>(words: I) -> WordCompleter
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        WordCompleter {
+            words: words.into_iter().map(|w| w.to_string()).collect(),
+            ignore_case: false,
+        }
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> WordCompleter {
+        self.ignore_case = ignore_case;
+        self
+    }
+}
+
+/// Suggests the first word that starts with the input
+impl Suggester for WordCompleter {
+    fn suggest(&self, input: &str) -> Option<String> {
+        self.complete(input).into_iter().next()
+    }
+}
+
+impl Completer for WordCompleter {
+    fn complete(&self, input: &str) -> Vec<String> {
+        let input = if self.ignore_case {
+            input.to_lowercase()
+        } else {
+            input.to_string()
+        };
+
+        self.words
+            .iter()
+            .filter(|word| {
+                if self.ignore_case {
+                    word.to_lowercase().starts_with(&input)
+                } else {
+                    word.starts_with(&input)
+                }
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Completes file and directory names, directories end with a `/`.
+///
+/// Hidden entries are only offered when the typed name starts with a dot.
+#[derive(Debug, Clone, Default)]
+pub struct PathCompleter {
+    dirs_only: bool,
+}
+
+impl PathCompleter {
+    pub fn new() -> PathCompleter {
+        PathCompleter::default()
+    }
+
+    /// Only offer directories
+    pub fn dirs_only(mut self, dirs_only: bool) -> PathCompleter {
+        self.dirs_only = dirs_only;
+        self
+    }
+}
+
+impl Completer for PathCompleter {
+    fn complete(&self, input: &str) -> Vec<String> {
+        let (dir, prefix) = match input.rfind('/') {
+            Some(idx) => input.split_at(idx + 1),
+            None => ("", input),
+        };
+
+        let path = if dir.is_empty() { "." } else { dir };
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut candidates = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.'))
+                {
+                    return None;
+                }
+                let is_dir = entry.path().is_dir();
+                if self.dirs_only && !is_dir {
+                    return None;
+                }
+                Some(format!("{}{}{}", dir, name, if is_dir { "/" } else { "" }))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates
+    }
+}
@@ -1,14 +1,16 @@
+use super::editor::{print_answer, read_answer};
 use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
 use super::{Editor, IntoEditor};
-use std::io::{stdin, stdout, Read, Write};
+use std::io::{Read, Write};
 use termion::cursor;
 use termion::event::Key;
 use termion::input::TermRead;
-use termion::raw::IntoRawMode;
 
 pub struct ConfirmBuilder<'de> {
     msg: &'de str,
+    name: Option<&'de str>,
     theme: Option<Theme>,
     default: bool,
 }
@@ -17,6 +19,7 @@ impl<'de> ConfirmBuilder<'de> {
     pub fn new(msg: &'de str) -> ConfirmBuilder<'de> {
         ConfirmBuilder {
             msg,
+            name: None,
             default: true,
             theme: None,
         }
@@ -27,9 +30,15 @@ impl<'de> ConfirmBuilder<'de> {
         self
     }
 
+    pub fn name(mut self, name: &'de str) -> ConfirmBuilder<'de> {
+        self.name = Some(name);
+        self
+    }
+
     pub fn build(self) -> Confirm<'de> {
         Confirm {
             msg: self.msg,
+            name: self.name,
             theme: self.theme,
             default: self.default,
         }
@@ -45,6 +54,7 @@ impl<'de> IntoEditor for ConfirmBuilder<'de> {
 
 pub struct Confirm<'de> {
     msg: &'de str,
+    name: Option<&'de str>,
     theme: Option<Theme>,
     default: bool,
 }
@@ -57,8 +67,8 @@ impl<'de> Confirm<'de> {
     pub fn run(&self) -> Result<bool> {
         <Confirm as Editor>::run(
             self,
-            &mut stdin(),
-            &mut stdout(),
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
             self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
         )
     }
@@ -67,13 +77,18 @@ impl<'de> Confirm<'de> {
 impl<'de> Editor for Confirm<'de> {
     type Output = bool;
 
-    fn run<R: Read, W: Write>(
+    fn run<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
     ) -> Result<Self::Output> {
-        let mut stdout = stdout.into_raw_mode()?;
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
 
         let w = theme.print_question(
             &mut stdout,
@@ -90,9 +105,12 @@ impl<'de> Editor for Confirm<'de> {
         stdout.flush()?;
 
         loop {
-            let next = input.next().unwrap();
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
 
-            match next? {
+            match next {
                 Key::Char('\n') => {
                     // Enter
                     break;
@@ -117,6 +135,32 @@ impl<'de> Editor for Confirm<'de> {
 
         Ok(choice)
     }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<bool> {
+        let choice = match answer.map(|a| a.trim().to_lowercase()).as_deref() {
+            None | Some("") => self.default,
+            Some("y") | Some("yes") => true,
+            Some("n") | Some("no") => false,
+            Some(other) => {
+                return Err(Error::InvalidAnswer(
+                    self.msg.to_string(),
+                    format!("expected y or n, got {:?}", other),
+                ))
+            }
+        };
+
+        print_answer(stdout, theme, self.msg, if choice { "yes" } else { "no" })?;
+        Ok(choice)
+    }
 }
 
 pub fn confirm<'de>(msg: &'de str) -> Result<bool> {
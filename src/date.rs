@@ -0,0 +1,301 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A day in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// `None` when the day doesn't exist, like February 30th
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    /// The current date in UTC, which is not the local date everywhere at
+    /// every time of day
+    pub fn today() -> Date {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Date::from_days(secs.div_euclid(86_400))
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// 1 to 12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Days since Monday, 0 to 6
+    pub fn weekday(&self) -> u8 {
+        (self.days() + 3).rem_euclid(7) as u8
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+
+    /// Move by whole months, keeping the day where the month is long enough
+    pub fn add_months(&self, months: i32) -> Date {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u8 + 1;
+        Date {
+            year,
+            month,
+            day: std::cmp::min(self.day, days_in_month(year, month)),
+        }
+    }
+
+    /// `March 2024`
+    pub(crate) fn month_title(&self) -> String {
+        format!("{} {}", MONTHS[self.month as usize - 1], self.year)
+    }
+
+    pub(crate) fn first_of_month(&self) -> Date {
+        Date { day: 1, ..*self }
+    }
+
+    /// Days since 1970-01-01
+    fn days(&self) -> i64 {
+        // From Howard Hinnant's `days_from_civil`
+        let y = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = self.month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    fn from_days(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `2024-03-15`
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Date, ParseDateError> {
+        let err = || ParseDateError(format!("{:?} is not a date like 2024-03-15", s));
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(err);
+        let (year, month, day) = (next()?, next()?, next()?);
+        Date::new(
+            year.parse().map_err(|_| err())?,
+            month.parse().map_err(|_| err())?,
+            day.parse().map_err(|_| err())?,
+        )
+        .ok_or_else(err)
+    }
+}
+
+/// A time of day with minute precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    /// `None` unless `hour < 24` and `minute < 60`
+    pub fn new(hour: u8, minute: u8) -> Option<Time> {
+        if hour > 23 || minute > 59 {
+            return None;
+        }
+        Some(Time { hour, minute })
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Move the hour by `hours`, wrapping around midnight
+    pub(crate) fn add_hours(&self, hours: i32) -> Time {
+        Time {
+            hour: (self.hour as i32 + hours).rem_euclid(24) as u8,
+            ..*self
+        }
+    }
+
+    /// Move the minute by `minutes`, wrapping within the hour
+    pub(crate) fn add_minutes(&self, minutes: i32) -> Time {
+        Time {
+            minute: (self.minute as i32 + minutes).rem_euclid(60) as u8,
+            ..*self
+        }
+    }
+}
+
+/// `09:30`
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Time, ParseDateError> {
+        let err = || ParseDateError(format!("{:?} is not a time like 09:30", s));
+        let (hour, minute) = s.trim().split_once(':').ok_or_else(err)?;
+        Time::new(
+            hour.parse().map_err(|_| err())?,
+            minute.parse().map_err(|_| err())?,
+        )
+        .ok_or_else(err)
+    }
+}
+
+/// A date with a time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Time) -> DateTime {
+        DateTime { date, time }
+    }
+}
+
+/// `2024-03-15 09:30`
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+/// Parses `2024-03-15 09:30` or `2024-03-15T09:30`
+impl FromStr for DateTime {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<DateTime, ParseDateError> {
+        match s.trim().split_once([' ', 'T']) {
+            Some((date, time)) => Ok(DateTime::new(date.parse()?, time.parse()?)),
+            None => Err(ParseDateError(format!(
+                "{:?} is not a date and time like 2024-03-15 09:30",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDateError(String);
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+/// Values a `DatePicker` can pick
+pub trait DateValue: fmt::Display + FromStr<Err = ParseDateError> + Ord + Copy {
+    /// Whether a time of day is picked along with the date
+    const HAS_TIME: bool;
+
+    fn from_parts(date: Date, time: Time) -> Self;
+    fn date(&self) -> Date;
+    fn time(&self) -> Time;
+}
+
+impl DateValue for Date {
+    const HAS_TIME: bool = false;
+
+    fn from_parts(date: Date, _: Time) -> Self {
+        date
+    }
+
+    fn date(&self) -> Date {
+        *self
+    }
+
+    fn time(&self) -> Time {
+        Time::default()
+    }
+}
+
+impl DateValue for DateTime {
+    const HAS_TIME: bool = true;
+
+    fn from_parts(date: Date, time: Time) -> Self {
+        DateTime::new(date, time)
+    }
+
+    fn date(&self) -> Date {
+        self.date
+    }
+
+    fn time(&self) -> Time {
+        self.time
+    }
+}
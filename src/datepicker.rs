@@ -0,0 +1,372 @@
+use super::date::{Date, DateTime, DateValue, Time};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use termion::{clear, cursor, event::Key, input::TermRead};
+
+/// A day drawn by `Theme::print_week`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarDay {
+    pub day: u8,
+    pub today: bool,
+    pub selected: bool,
+    /// Outside the picker's min/max
+    pub disabled: bool,
+}
+
+/// Part of a `DatePicker` the keys act on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Focus {
+    Calendar,
+    Hour,
+    Minute,
+}
+
+pub struct DatePickerBuilder<'a, T: DateValue = Date> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    minute_step: u8,
+    today: Option<Date>,
+}
+
+impl<'a> DatePickerBuilder<'a> {
+    pub fn new(msg: &'a str) -> DatePickerBuilder<'a> {
+        DatePickerBuilder {
+            msg,
+            name: None,
+            theme: None,
+            default: None,
+            min: None,
+            max: None,
+            minute_step: 1,
+            today: None,
+        }
+    }
+
+    /// Also pick a time of day, Tab moves between the days, hours and
+    /// minutes
+    pub fn with_time(self) -> DatePickerBuilder<'a, DateTime> {
+        let midnight = Time::default();
+        let last_minute = Time::new(23, 59).unwrap();
+        DatePickerBuilder {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            default: self.default.map(|d| DateTime::new(d, midnight)),
+            min: self.min.map(|d| DateTime::new(d, midnight)),
+            max: self.max.map(|d| DateTime::new(d, last_minute)),
+            minute_step: self.minute_step,
+            today: self.today,
+        }
+    }
+}
+
+impl<'a, T: DateValue> DatePickerBuilder<'a, T> {
+    pub fn theme(mut self, theme: Theme) -> DatePickerBuilder<'a, T> {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Value selected at the start, instead of today
+    pub fn default(mut self, default: T) -> DatePickerBuilder<'a, T> {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn min(mut self, min: T) -> DatePickerBuilder<'a, T> {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: T) -> DatePickerBuilder<'a, T> {
+        self.max = Some(max);
+        self
+    }
+
+    /// Amount Up/Down change the minutes by
+    pub fn minute_step(mut self, step: u8) -> DatePickerBuilder<'a, T> {
+        self.minute_step = std::cmp::max(step, 1);
+        self
+    }
+
+    /// Date marked and jumped to as today, `Date::today()` in UTC by
+    /// default. Pass the local date in where that differs.
+    pub fn today(mut self, today: Date) -> DatePickerBuilder<'a, T> {
+        self.today = Some(today);
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> DatePickerBuilder<'a, T> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> DatePicker<'a, T> {
+        DatePicker {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            default: self.default,
+            min: self.min,
+            max: self.max,
+            minute_step: self.minute_step,
+            today: self.today,
+        }
+    }
+}
+
+impl<'a, T: DateValue> IntoEditor for DatePickerBuilder<'a, T> {
+    type Editor = DatePicker<'a, T>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Picks a date from a calendar, and optionally a time of day.
+///
+/// Left/Right move a day, Up/Down a week and PgUp/PgDn a month.
+pub struct DatePicker<'a, T: DateValue = Date> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    minute_step: u8,
+    today: Option<Date>,
+}
+
+impl<'a> DatePicker<'a> {
+    pub fn new(msg: &'a str) -> DatePickerBuilder<'a> {
+        DatePickerBuilder::new(msg)
+    }
+}
+
+impl<'a, T: DateValue> DatePicker<'a, T> {
+    pub fn run(&self) -> Result<T> {
+        <DatePicker<T> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn clamp(&self, value: T) -> T {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        }
+    }
+
+    fn check(&self, value: T) -> std::result::Result<T, String> {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => Err(format!("Must be {} or later", min)),
+            (_, Some(max)) if value > max => Err(format!("Must be {} or earlier", max)),
+            _ => Ok(value),
+        }
+    }
+
+    fn is_disabled(&self, date: Date) -> bool {
+        matches!(self.min, Some(min) if date < min.date())
+            || matches!(self.max, Some(max) if date > max.date())
+    }
+
+    /// Lines drawn below the question
+    fn lines(&self) -> usize {
+        if T::HAS_TIME {
+            9
+        } else {
+            8
+        }
+    }
+
+    /// Draw the question and the calendar, leaving the cursor on the question
+    fn render<W: Terminal>(
+        &self,
+        stdout: &mut W,
+        theme: &Theme,
+        value: T,
+        today: Date,
+        focus: Focus,
+    ) -> Result<()> {
+        theme.print_question(stdout, self.msg, None)?;
+        write!(stdout, "{}", value)?;
+
+        let date = value.date();
+        write!(stdout, "\r\n")?;
+        theme.print_calendar_title(stdout, &date.month_title())?;
+        write!(stdout, "\r\n")?;
+        theme.print_weekdays(stdout)?;
+
+        let first = date.first_of_month();
+        let mut day = first.add_days(-(first.weekday() as i64));
+        for _ in 0..6 {
+            let week = (0..7)
+                .map(|_| {
+                    let cell = if day.month() == date.month() {
+                        Some(CalendarDay {
+                            day: day.day(),
+                            today: day == today,
+                            selected: day == date,
+                            disabled: self.is_disabled(day),
+                        })
+                    } else {
+                        None
+                    };
+                    day = day.add_days(1);
+                    cell
+                })
+                .collect::<Vec<_>>();
+            write!(stdout, "\r\n")?;
+            theme.print_week(stdout, &week)?;
+        }
+
+        if T::HAS_TIME {
+            write!(stdout, "\r\n")?;
+            theme.print_time(
+                stdout,
+                value.time(),
+                focus == Focus::Hour,
+                focus == Focus::Minute,
+            )?;
+        }
+
+        write!(stdout, "{}\r", cursor::Up(self.lines() as u16))?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// Clear everything drawn below the question, leaving the cursor on it
+    fn clear<W: Terminal>(&self, stdout: &mut W) -> Result<()> {
+        for _ in 0..self.lines() {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        }
+        write!(stdout, "{}\r", cursor::Up(self.lines() as u16))?;
+        Ok(())
+    }
+}
+
+impl<'a, T: DateValue> Editor for DatePicker<'a, T> {
+    type Output = T;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let today = self.today.unwrap_or_else(Date::today);
+        let mut value = self.clamp(
+            self.default
+                .unwrap_or_else(|| T::from_parts(today, Time::default())),
+        );
+        let mut focus = Focus::Calendar;
+
+        write!(stdout, "{}", cursor::Hide)?;
+
+        loop {
+            self.render(&mut stdout, theme, value, today, focus)?;
+
+            let key = match keys.next() {
+                Some(key) => key?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            let (date, time) = (value.date(), value.time());
+            let step = self.minute_step as i32;
+            let next = match (focus, key) {
+                (_, Key::Char('\n')) => break,
+                (_, Key::Ctrl('c')) => {
+                    self.clear(&mut stdout)?;
+                    write!(stdout, "\n\r{}", cursor::Show)?;
+                    return Err(Error::UserAborted);
+                }
+                (_, Key::Char('\t')) if T::HAS_TIME => {
+                    focus = match focus {
+                        Focus::Calendar => Focus::Hour,
+                        Focus::Hour => Focus::Minute,
+                        Focus::Minute => Focus::Calendar,
+                    };
+                    continue;
+                }
+                (_, Key::BackTab) if T::HAS_TIME => {
+                    focus = match focus {
+                        Focus::Calendar => Focus::Minute,
+                        Focus::Hour => Focus::Calendar,
+                        Focus::Minute => Focus::Hour,
+                    };
+                    continue;
+                }
+                (_, Key::PageUp) => T::from_parts(date.add_months(-1), time),
+                (_, Key::PageDown) => T::from_parts(date.add_months(1), time),
+                (Focus::Calendar, Key::Left) => T::from_parts(date.add_days(-1), time),
+                (Focus::Calendar, Key::Right) => T::from_parts(date.add_days(1), time),
+                (Focus::Calendar, Key::Up) => T::from_parts(date.add_days(-7), time),
+                (Focus::Calendar, Key::Down) => T::from_parts(date.add_days(7), time),
+                (Focus::Calendar, Key::Char('t')) => T::from_parts(today, time),
+                (Focus::Hour, Key::Up) => T::from_parts(date, time.add_hours(1)),
+                (Focus::Hour, Key::Down) => T::from_parts(date, time.add_hours(-1)),
+                (Focus::Minute, Key::Up) => T::from_parts(date, time.add_minutes(step)),
+                (Focus::Minute, Key::Down) => T::from_parts(date, time.add_minutes(-step)),
+                (Focus::Hour, Key::Right) => {
+                    focus = Focus::Minute;
+                    continue;
+                }
+                (Focus::Minute, Key::Left) => {
+                    focus = Focus::Hour;
+                    continue;
+                }
+                _ => continue,
+            };
+            value = self.clamp(next);
+        }
+
+        self.clear(&mut stdout)?;
+        write!(stdout, "{}", cursor::Show)?;
+        theme.print_results(&mut stdout, self.msg, &value.to_string())?;
+        Ok(value)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<T> {
+        let value = match (answer, self.default) {
+            (Some(answer), _) if !answer.trim().is_empty() => answer
+                .parse::<T>()
+                .map_err(|err| err.to_string())
+                .and_then(|value| self.check(value))
+                .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?,
+            (_, Some(default)) => default,
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        print_answer(stdout, theme, self.msg, &value.to_string())?;
+        Ok(value)
+    }
+}
+
+pub fn pick_date(msg: &str) -> Result<Date> {
+    DatePickerBuilder::new(msg).build().run()
+}
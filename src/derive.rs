@@ -0,0 +1,91 @@
+use super::choice::Choice;
+use super::error::Result;
+use super::form::Form;
+use super::multiselect::MultiSelect;
+use super::select::Select;
+use super::terminal::Terminal;
+use std::io::Read;
+
+/// Types that can be filled in by asking for each field,
+/// usually implemented with `#[derive(Prompt)]`
+pub trait Prompt: Sized {
+    fn prompt<R: Read, W: Terminal>(form: &mut Form<R, W>) -> Result<Self>;
+}
+
+/// Enums whose variants are offered as choices,
+/// usually implemented with `#[derive(Prompt)]`
+pub trait Variants: Choice + Sized {
+    fn variants() -> Vec<Self>;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub fn select<V, R, W>(form: &mut Form<R, W>, msg: &str, name: &str) -> Result<V>
+    where
+        V: Variants,
+        R: Read,
+        W: Terminal,
+    {
+        let mut variants = V::variants();
+        let idx = {
+            let choices = variants.iter().map(Variant).collect::<Vec<_>>();
+            form.run(Select::new(msg, choices).name(name).indexed())?
+                .index
+        };
+        Ok(variants.swap_remove(idx))
+    }
+
+    pub fn multi_select<V, R, W>(form: &mut Form<R, W>, msg: &str, name: &str) -> Result<Vec<V>>
+    where
+        V: Variants,
+        R: Read,
+        W: Terminal,
+    {
+        let variants = V::variants();
+        let choices = variants.iter().map(Variant).collect::<Vec<_>>();
+        let indices = form
+            .run(MultiSelect::new(msg, choices).name(name).indexed())?
+            .into_iter()
+            .map(|selection| selection.index)
+            .collect::<Vec<_>>();
+
+        let mut variants = variants.into_iter().map(Some).collect::<Vec<_>>();
+        Ok(indices
+            .into_iter()
+            .filter_map(|idx| variants[idx].take())
+            .collect())
+    }
+
+    /// A variant offered as a choice. Only the index of the variant picked
+    /// is needed, so variants don't have to be `Clone`.
+    struct Variant<'a, V>(&'a V);
+
+    impl<'a, V> Clone for Variant<'a, V> {
+        fn clone(&self) -> Self {
+            Variant(self.0)
+        }
+    }
+
+    impl<'a, V: Choice> Choice for Variant<'a, V> {
+        type Text = V::Text;
+        type Value = ();
+
+        fn text(&self) -> &Self::Text {
+            self.0.text()
+        }
+
+        fn value(&self) -> &Self::Value {
+            &()
+        }
+
+        fn disabled(&self) -> Option<&str> {
+            self.0.disabled()
+        }
+
+        fn is_separator(&self) -> bool {
+            self.0.is_separator()
+        }
+    }
+}
@@ -1,16 +1,41 @@
-use super::error::Result;
+use super::error::{Error, Result};
+use super::terminal::Terminal;
 use super::theme::Theme;
-use std::io::{Read, Write};
+use std::io::{self, Read};
 
 pub trait Editor {
     type Output;
 
-    fn run<R: Read, W: Write>(
+    fn run<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
     ) -> Result<Self::Output>;
+
+    /// Key identifying the prompt when looking up pre-seeded answers, set
+    /// with `name` on the builders
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Produce the output from an answer given up front instead of asking
+    /// the user. `None` means no answer was given, in which case the
+    /// default is used if there is one.
+    ///
+    /// The answer is validated like typed input and echoed as a result.
+    /// Editors that cannot take an answer report it as missing, naming
+    /// themselves by their type when they have no name.
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        let _ = (answer, stdout, theme);
+        let name = self.name().unwrap_or_else(|| std::any::type_name::<Self>());
+        Err(Error::NoAnswer(name.to_string()))
+    }
 }
 
 pub trait IntoEditor {
@@ -28,6 +53,64 @@ where
     }
 }
 
+/// Read a line of the plain protocol used when not interactive.
+/// Returns `None` when the input is exhausted.
+pub(crate) fn read_answer<R: Read>(stdin: &mut R) -> Result<Option<String>> {
+    let mut buf = Vec::new();
+    let mut byte = [0u8];
+
+    loop {
+        if stdin.read(&mut byte)? == 0 {
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        if byte[0] == b'\n' {
+            break;
+        }
+        buf.push(byte[0]);
+    }
+
+    let line = String::from_utf8(buf)
+        .map_err(|e| Error::IoError(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    Ok(Some(line.trim_end_matches('\r').to_string()))
+}
+
+/// Read text of several lines in the plain protocol, up to a line with a
+/// single `.` or the end of the input. Returns `None` when the input is
+/// exhausted.
+pub(crate) fn read_text<R: Read>(stdin: &mut R) -> Result<Option<String>> {
+    let mut lines = Vec::new();
+    while let Some(line) = read_answer(stdin)? {
+        if line == "." {
+            return Ok(Some(lines.join("\n")));
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(lines.join("\n")))
+    }
+}
+
+/// Echo an answer that was not typed by the user
+pub(crate) fn print_answer<W: Terminal>(
+    stdout: &mut W,
+    theme: &Theme,
+    msg: &str,
+    ans: &str,
+) -> Result<()> {
+    if stdout.is_interactive() {
+        theme.print_results(stdout, msg, ans)?;
+    } else {
+        theme.print_plain_results(stdout, msg, ans)?;
+    }
+    Ok(())
+}
+
 // pub struct EditorPrinter<'a> {
 //     inline: bool,
 //     label: &'a str,
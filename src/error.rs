@@ -10,6 +10,10 @@ pub enum Error {
     NoMoreInput,
     UserAborted,
     InvalidChoice(usize),
+    /// No answer could be found for the prompt when not interactive
+    NoAnswer(String),
+    /// The answer given to the prompt when not interactive was rejected
+    InvalidAnswer(String, String),
     Format(fmt::Error),
 }
 
@@ -32,6 +36,10 @@ impl fmt::Display for Error {
             Error::NoMoreInput => write!(f, "No more input"),
             Error::UserAborted => write!(f, "User aborted"),
             Error::InvalidChoice(idx) => write!(f, "Invalid choice at inedx: {}", idx),
+            Error::NoAnswer(msg) => write!(f, "No answer for prompt: {}", msg),
+            Error::InvalidAnswer(msg, err) => {
+                write!(f, "Invalid answer for prompt {}: {}", msg, err)
+            }
             Error::Format(err) => write!(f, "Formatting error: {}", err),
         }
     }
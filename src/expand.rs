@@ -0,0 +1,260 @@
+use super::choice::{find_choice, Choice, Keyed};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::{clear, cursor};
+
+/// Key expanding the hint into a list of all choices
+const HELP_KEY: char = 'h';
+
+pub struct ExpandBuilder<'de, C> {
+    msg: &'de str,
+    name: Option<&'de str>,
+    choices: &'de [Keyed<C>],
+    default: Option<char>,
+    theme: Option<Theme>,
+}
+
+impl<'de, C: Choice> ExpandBuilder<'de, C> {
+    pub fn new(msg: &'de str, choices: &'de [Keyed<C>]) -> ExpandBuilder<'de, C> {
+        ExpandBuilder {
+            msg,
+            name: None,
+            choices,
+            default: None,
+            theme: None,
+        }
+    }
+
+    /// Key of the choice Enter picks
+    pub fn default(mut self, key: char) -> ExpandBuilder<'de, C> {
+        self.default = Some(key);
+        self
+    }
+
+    pub fn name(mut self, name: &'de str) -> ExpandBuilder<'de, C> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Expand<'de, C> {
+        let choices = self.choices;
+        Expand {
+            msg: self.msg,
+            name: self.name,
+            choices,
+            default: self.default.and_then(|key| position(choices, key)),
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'de, C: Choice> IntoEditor for ExpandBuilder<'de, C> {
+    type Editor = Expand<'de, C>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Picks a choice with a single key, like `git add -p`.
+///
+/// The question shows the keys as a compact hint, `h` expands it into a
+/// list of the choices. Choices sharing a key, or using `h`, are reported
+/// as an invalid answer.
+pub struct Expand<'de, C> {
+    msg: &'de str,
+    name: Option<&'de str>,
+    choices: &'de [Keyed<C>],
+    default: Option<usize>,
+    theme: Option<Theme>,
+}
+
+fn position<C>(choices: &[Keyed<C>], key: char) -> Option<usize> {
+    let key = key.to_ascii_lowercase();
+    choices
+        .iter()
+        .position(|c| c.key().to_ascii_lowercase() == key)
+}
+
+impl<'de, C: Choice> Expand<'de, C> {
+    pub fn new(msg: &'de str, choices: &'de [Keyed<C>]) -> ExpandBuilder<'de, C> {
+        ExpandBuilder::new(msg, choices)
+    }
+
+    pub fn run(&self) -> Result<&'de C> {
+        <Expand<'de, C> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    /// Fail when two choices share a key, or a choice uses `h`
+    fn check_keys(&self) -> Result<()> {
+        for (i, choice) in self.choices.iter().enumerate() {
+            let key = choice.key().to_ascii_lowercase();
+            let err = if key == HELP_KEY {
+                format!("`{}` is reserved for help", HELP_KEY)
+            } else if position(&self.choices[..i], key).is_some() {
+                format!("duplicate key `{}`", key)
+            } else {
+                continue;
+            };
+            return Err(Error::InvalidAnswer(self.msg.to_string(), err));
+        }
+        Ok(())
+    }
+
+    /// `yNadh`, with the default in upper case
+    fn hint(&self) -> String {
+        self.choices
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if Some(i) == self.default {
+                    c.key().to_ascii_uppercase()
+                } else {
+                    c.key().to_ascii_lowercase()
+                }
+            })
+            .chain(std::iter::once(HELP_KEY))
+            .collect()
+    }
+
+    /// Rows drawn below the question when expanded
+    fn rows(&self) -> usize {
+        self.choices.len() + 1
+    }
+
+    /// Clear the expanded list, leaving the cursor on the question
+    fn clear<W: Terminal>(&self, stdout: &mut W) -> Result<()> {
+        for _ in 0..self.rows() {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        }
+        write!(stdout, "{}", cursor::Up(self.rows() as u16))?;
+        Ok(())
+    }
+}
+
+impl<'de, C: Choice> Editor for Expand<'de, C> {
+    type Output = &'de C;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.check_keys()?;
+
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let hint = self.hint();
+        let mut input = stdin.keys();
+        let mut expanded = false;
+
+        let idx = loop {
+            let w = theme.print_question(&mut stdout, self.msg, Some(&hint))?;
+            if expanded {
+                for (i, choice) in self.choices.iter().enumerate() {
+                    write!(stdout, "\r\n")?;
+                    theme.print_keyed_choice(
+                        &mut stdout,
+                        choice.key(),
+                        &choice.text().to_string(),
+                        Some(i) == self.default,
+                    )?;
+                }
+                write!(stdout, "\r\n")?;
+                theme.print_keyed_choice(&mut stdout, HELP_KEY, "Help, list all options", false)?;
+                write!(
+                    stdout,
+                    "{}\r{}",
+                    cursor::Up(self.rows() as u16),
+                    cursor::Right(w as u16)
+                )?;
+            }
+            stdout.flush()?;
+
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            match next {
+                Key::Char('\n') => {
+                    if let Some(idx) = self.default {
+                        break idx;
+                    }
+                }
+                Key::Char(HELP_KEY) => expanded = true,
+                Key::Ctrl('c') => {
+                    if expanded {
+                        self.clear(&mut stdout)?;
+                    }
+                    write!(stdout, "\n\r{}", cursor::Show)?;
+                    return Err(Error::UserAborted);
+                }
+                Key::Char(c) => {
+                    if let Some(idx) = position(self.choices, c) {
+                        break idx;
+                    }
+                }
+                _ => {}
+            }
+        };
+
+        if expanded {
+            self.clear(&mut stdout)?;
+        }
+
+        let choice = &self.choices[idx];
+        theme.print_results(&mut stdout, self.msg, &choice.text().to_string())?;
+        Ok(choice.choice())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    /// Takes a key, the text of a choice or its index
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<&'de C> {
+        self.check_keys()?;
+
+        let answer = answer.map(str::trim).unwrap_or("");
+        let mut chars = answer.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(key), None) => position(self.choices, key),
+            _ => None,
+        };
+        let idx = match key {
+            Some(idx) => idx,
+            None if answer.is_empty() => self
+                .default
+                .ok_or_else(|| Error::NoAnswer(self.msg.to_string()))?,
+            None => find_choice(self.msg, self.choices, answer)?,
+        };
+
+        let choice = &self.choices[idx];
+        print_answer(stdout, theme, self.msg, &choice.text().to_string())?;
+        Ok(choice.choice())
+    }
+}
+
+pub fn expand<'de, C: Choice>(msg: &'de str, choices: &'de [Keyed<C>]) -> Result<&'de C> {
+    ExpandBuilder::new(msg, choices).build().run()
+}
@@ -0,0 +1,271 @@
+use super::editor::{print_answer, read_text, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::textarea::summary;
+use super::theme::{Theme, DEFAULT_THEME};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use termion::screen::{ToAlternateScreen, ToMainScreen};
+use termion::{clear, cursor, event::Key, input::TermRead};
+use valid::{Valid, Validation};
+
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+pub struct ExternalEditorBuilder<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    template: Option<&'a str>,
+    extension: &'a str,
+    command: Option<&'a str>,
+    validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+}
+
+impl<'a> ExternalEditorBuilder<'a> {
+    pub fn new(msg: &'a str) -> ExternalEditorBuilder<'a> {
+        ExternalEditorBuilder {
+            msg,
+            name: None,
+            theme: None,
+            template: None,
+            extension: ".txt",
+            command: None,
+            validations: None,
+        }
+    }
+
+    pub fn validate<V: Validation<String> + 'static>(mut self, v: V) -> Self {
+        if let Some(valid) = self.validations.take() {
+            self.validations = Some(valid.and_boxed(v));
+        } else {
+            self.validations = Some(Valid::new(Box::new(v)));
+        }
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> ExternalEditorBuilder<'a> {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Text the file starts with
+    pub fn template(mut self, template: &'a str) -> ExternalEditorBuilder<'a> {
+        self.template = Some(template);
+        self
+    }
+
+    /// Extension of the temporary file, so the editor picks the right
+    /// syntax highlighting
+    pub fn extension(mut self, extension: &'a str) -> ExternalEditorBuilder<'a> {
+        self.extension = extension;
+        self
+    }
+
+    /// Editor to run instead of `$VISUAL` or `$EDITOR`. Like those it is
+    /// run by the shell, with the file as its last argument.
+    pub fn command(mut self, command: &'a str) -> ExternalEditorBuilder<'a> {
+        self.command = Some(command);
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> ExternalEditorBuilder<'a> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> ExternalEditor<'a> {
+        ExternalEditor {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            template: self.template,
+            extension: self.extension,
+            command: self.command,
+            validations: self.validations,
+        }
+    }
+}
+
+impl<'a> IntoEditor for ExternalEditorBuilder<'a> {
+    type Editor = ExternalEditor<'a>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Opens `$VISUAL` or `$EDITOR` on a temporary file, like `git commit`
+pub struct ExternalEditor<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    template: Option<&'a str>,
+    extension: &'a str,
+    command: Option<&'a str>,
+    validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+}
+
+impl<'a> ExternalEditor<'a> {
+    pub fn new(msg: &'a str) -> ExternalEditorBuilder<'a> {
+        ExternalEditorBuilder::new(msg)
+    }
+
+    pub fn run(&self) -> Result<String> {
+        <ExternalEditor as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn command(&self) -> String {
+        match self.command {
+            Some(command) => command.to_owned(),
+            None => env::var("VISUAL")
+                .or_else(|_| env::var("EDITOR"))
+                .ok()
+                .filter(|editor| !editor.trim().is_empty())
+                .unwrap_or_else(|| "vi".to_owned()),
+        }
+    }
+
+    fn validate(&self, text: &str) -> std::result::Result<(), String> {
+        match &self.validations {
+            Some(v) => v.validate(&text.to_owned()).map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Let the user edit `content` in the editor and return the result
+    fn edit(&self, content: &str) -> Result<String> {
+        let path = temp_file(self.extension);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        file.write_all(content.as_bytes())?;
+        // Editors expect text files to end with a newline
+        if !content.is_empty() && !content.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        drop(file);
+
+        // Like git, let the shell split the command so it can be quoted
+        let command = self.command();
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$@\"", command))
+            .arg(&command)
+            .arg(&path)
+            .status();
+
+        let text = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        let status = status?;
+        if !status.success() {
+            let err = format!("{} exited with {}", command, status);
+            return Err(io::Error::other(err).into());
+        }
+
+        Ok(text?.trim_end_matches('\n').to_owned())
+    }
+}
+
+fn temp_file(extension: &str) -> PathBuf {
+    let n = TEMP_FILES.fetch_add(1, Ordering::SeqCst);
+    env::temp_dir().join(format!("prompt-{}-{}{}", process::id(), n, extension))
+}
+
+impl<'a> Editor for ExternalEditor<'a> {
+    type Output = String;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_text(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let mut content = self.template.unwrap_or("").to_owned();
+        let mut error: Option<String> = None;
+
+        loop {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+            if let Some(error) = &error {
+                theme.print_error(&mut stdout, error)?;
+            }
+            write!(stdout, "{}\r", cursor::Up(1))?;
+            theme.print_question(&mut stdout, self.msg, None)?;
+            theme.print_hint(&mut stdout, "Press <enter> to edit")?;
+            stdout.flush()?;
+
+            loop {
+                match keys.next() {
+                    Some(key) => match key? {
+                        Key::Char('\n') => break,
+                        Key::Ctrl('c') => {
+                            write!(stdout, "\r\n{}\n\r", clear::CurrentLine)?;
+                            return Err(Error::UserAborted);
+                        }
+                        _ => {}
+                    },
+                    None => return Err(Error::NoMoreInput),
+                }
+            }
+
+            // The editor gets a screen of its own, in the mode the terminal
+            // was in before
+            write!(stdout, "{}", ToAlternateScreen)?;
+            stdout.flush()?;
+            stdout.set_raw_mode(false)?;
+            let text = self.edit(&content);
+            stdout.set_raw_mode(true)?;
+            write!(stdout, "{}", ToMainScreen)?;
+            let text = text?;
+
+            match self.validate(&text) {
+                Ok(()) => {
+                    content = text;
+                    break;
+                }
+                Err(err) => {
+                    error = Some(err);
+                    content = text;
+                }
+            }
+        }
+
+        // Clear a previous validation error below the question
+        write!(stdout, "\r\n{}{}", clear::CurrentLine, cursor::Up(1))?;
+        theme.print_results(&mut stdout, self.msg, &summary(&content))?;
+        Ok(content)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<String> {
+        let text = answer.ok_or_else(|| Error::NoAnswer(self.msg.to_string()))?;
+        self.validate(text)
+            .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?;
+        print_answer(stdout, theme, self.msg, &summary(text))?;
+        Ok(text.to_owned())
+    }
+}
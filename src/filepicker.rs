@@ -0,0 +1,402 @@
+use super::choice::{Choice, Cursor};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::fuzzy::fuzzy_match;
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::{clear, cursor};
+
+/// A row of a `FilePicker`
+struct Entry {
+    /// The name, directories end with a `/`
+    text: String,
+    path: PathBuf,
+    dir: bool,
+}
+
+impl Choice for Entry {
+    type Text = String;
+    type Value = PathBuf;
+
+    fn text(&self) -> &String {
+        &self.text
+    }
+
+    fn value(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+pub struct FilePickerBuilder<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    start: PathBuf,
+    hidden: bool,
+    extensions: Vec<String>,
+    dirs_only: bool,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a> FilePickerBuilder<'a> {
+    pub fn new(msg: &'a str) -> FilePickerBuilder<'a> {
+        FilePickerBuilder {
+            msg,
+            name: None,
+            start: PathBuf::from("."),
+            hidden: false,
+            extensions: Vec::new(),
+            dirs_only: false,
+            page_size: 8,
+            theme: None,
+        }
+    }
+
+    /// Directory to start browsing in, the current directory by default
+    pub fn start(mut self, start: impl AsRef<Path>) -> FilePickerBuilder<'a> {
+        self.start = start.as_ref().to_path_buf();
+        self
+    }
+
+    /// Show entries starting with a dot
+    pub fn hidden(mut self, hidden: bool) -> FilePickerBuilder<'a> {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Only show files with one of these extensions, like `&["rs", "toml"]`
+    pub fn extensions(mut self, extensions: &[&str]) -> FilePickerBuilder<'a> {
+        self.extensions = extensions
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Pick a directory instead of a file, the first row picks the
+    /// directory being browsed
+    pub fn dirs_only(mut self, dirs_only: bool) -> FilePickerBuilder<'a> {
+        self.dirs_only = dirs_only;
+        self
+    }
+
+    pub fn page_size(mut self, size: usize) -> FilePickerBuilder<'a> {
+        self.page_size = size;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> FilePickerBuilder<'a> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> FilePicker<'a> {
+        FilePicker {
+            msg: self.msg,
+            name: self.name,
+            start: self.start,
+            hidden: self.hidden,
+            extensions: self.extensions,
+            dirs_only: self.dirs_only,
+            page_size: std::cmp::max(self.page_size, 1),
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'a> IntoEditor for FilePickerBuilder<'a> {
+    type Editor = FilePicker<'a>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Browses the filesystem for a file or directory.
+///
+/// Enter opens the highlighted directory or picks the highlighted file,
+/// Backspace goes up a directory and typing filters the entries. The path
+/// returned is canonical.
+pub struct FilePicker<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    start: PathBuf,
+    hidden: bool,
+    extensions: Vec<String>,
+    dirs_only: bool,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a> FilePicker<'a> {
+    pub fn new(msg: &'a str) -> FilePickerBuilder<'a> {
+        FilePickerBuilder::new(msg)
+    }
+
+    pub fn run(&self) -> Result<PathBuf> {
+        <FilePicker as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn is_hidden(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with('.'))
+            .unwrap_or(false)
+    }
+
+    fn accepts(&self, path: &Path, dir: bool) -> bool {
+        if !self.hidden && Self::is_hidden(path) {
+            return false;
+        }
+        if dir {
+            return true;
+        }
+        if self.dirs_only {
+            return false;
+        }
+        self.extensions.is_empty()
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| self.extensions.contains(&e.to_lowercase()))
+                .unwrap_or(false)
+    }
+
+    /// Entries of `dir`, directories first
+    fn entries(&self, dir: &Path) -> io::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        if self.dirs_only {
+            entries.push(Entry {
+                text: "./".to_string(),
+                path: dir.to_path_buf(),
+                dir: false,
+            });
+        }
+
+        let mut found = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let dir = path.is_dir();
+                if !self.accepts(&path, dir) {
+                    return None;
+                }
+                let name = entry.file_name().to_string_lossy().into_owned();
+                Some(Entry {
+                    text: if dir { format!("{}/", name) } else { name },
+                    path,
+                    dir,
+                })
+            })
+            .collect::<Vec<_>>();
+        found.sort_by(|a, b| b.dir.cmp(&a.dir).then_with(|| a.text.cmp(&b.text)));
+        entries.extend(found);
+        Ok(entries)
+    }
+
+    /// Indices into `entries` matching `query`, best match first, together
+    /// with the matched char positions
+    fn filtered(&self, entries: &[Entry], query: &str) -> Vec<(usize, Vec<usize>)> {
+        if query.is_empty() {
+            return (0..entries.len()).map(|i| (i, Vec::new())).collect();
+        }
+
+        let mut matches = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| fuzzy_match(query, &e.text).map(|m| (i, m)))
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.1.score));
+
+        matches.into_iter().map(|(i, m)| (i, m.indices)).collect()
+    }
+
+    /// `/home/user/`, cut to the last `width` chars so the question
+    /// doesn't wrap
+    fn display(dir: &Path, width: usize) -> String {
+        let mut dir = dir.to_string_lossy().into_owned();
+        if !dir.ends_with('/') {
+            dir.push('/');
+        }
+        let len = dir.chars().count();
+        if len <= width {
+            return dir;
+        }
+        let tail = dir.chars().skip(len + 1 - width).collect::<String>();
+        format!("…{}", tail)
+    }
+}
+
+impl<'a> Editor for FilePicker<'a> {
+    type Output = PathBuf;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut dir = fs::canonicalize(&self.start)?;
+        let mut entries = self.entries(&dir)?;
+        let mut stdout = RawMode::new(stdout)?;
+        let rows = self.page_size;
+        let width = stdout.size()?.0 as usize;
+
+        let mut query = String::new();
+        let mut filtered = self.filtered(&entries, &query);
+        let mut cursor = Cursor::new(filtered.len(), rows, 0, |_| true);
+        let mut error: Option<String> = None;
+
+        let mut input = stdin.keys();
+
+        write!(stdout, "{}", cursor::Hide)?;
+
+        let path = loop {
+            let visible = std::cmp::min(filtered.len(), rows);
+
+            let w = theme.print_question(&mut stdout, self.msg, None)?;
+            let room = width.saturating_sub(w + query.chars().count() + 1);
+            write!(stdout, "{}{}", Self::display(&dir, room), query)?;
+
+            let page = filtered.iter().enumerate().skip(cursor.offset).take(rows);
+            for (i, (idx, matches)) in page {
+                let entry = &entries[*idx];
+                write!(&mut stdout, "\n\r{}", clear::CurrentLine)?;
+                if query.is_empty() {
+                    theme.print_choice(&mut stdout, entry, cursor.pos == i)?;
+                } else {
+                    theme.print_filtered_choice(&mut stdout, entry, cursor.pos == i, matches)?;
+                }
+            }
+
+            for _ in visible..rows {
+                write!(&mut stdout, "\n\r{}", clear::CurrentLine)?;
+            }
+
+            write!(stdout, "\n\r{}", clear::CurrentLine)?;
+            if let Some(error) = &error {
+                theme.print_error(&mut stdout, error)?;
+            }
+            write!(stdout, "{}", cursor::Up(rows as u16 + 1))?;
+            stdout.flush()?;
+
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            let mut opened = None;
+            match next {
+                Key::Char('\n') if visible > 0 => {
+                    let entry = &entries[filtered[cursor.pos].0];
+                    if !entry.dir {
+                        break entry.path.clone();
+                    }
+                    opened = Some(entry.path.clone());
+                }
+                Key::Up => cursor.up(|_| true),
+                Key::Down => cursor.down(|_| true),
+                Key::Backspace if !query.is_empty() => {
+                    query.pop();
+                    filtered = self.filtered(&entries, &query);
+                    cursor = Cursor::new(filtered.len(), rows, 0, |_| true);
+                }
+                Key::Backspace => {
+                    opened = dir.parent().map(Path::to_path_buf);
+                }
+                Key::Char(c) if c != '\n' && c != '\t' => {
+                    query.push(c);
+                    filtered = self.filtered(&entries, &query);
+                    cursor = Cursor::new(filtered.len(), rows, 0, |_| true);
+                }
+                Key::Ctrl('c') => {
+                    for _ in 0..=rows {
+                        write!(stdout, "\n\r{}", clear::CurrentLine)?;
+                    }
+                    write!(
+                        stdout,
+                        "{}\n\r{}",
+                        cursor::Up(rows as u16 + 1),
+                        cursor::Show
+                    )?;
+                    return Err(Error::UserAborted);
+                }
+                _ => {}
+            }
+
+            if let Some(opened) = opened {
+                match self.entries(&opened) {
+                    Ok(opened_entries) => {
+                        // Going up highlights the directory we came from
+                        let came_from = opened_entries.iter().position(|e| e.path == dir);
+                        entries = opened_entries;
+                        dir = opened;
+                        query.clear();
+                        filtered = self.filtered(&entries, &query);
+                        let start = came_from.unwrap_or(0);
+                        cursor = Cursor::new(filtered.len(), rows, start, |_| true);
+                        error = None;
+                    }
+                    Err(err) => error = Some(err.to_string()),
+                }
+            }
+        };
+
+        for _ in 0..=rows {
+            write!(stdout, "\n\r{}", clear::CurrentLine)?;
+        }
+        write!(stdout, "{}\r{}", cursor::Up(rows as u16 + 1), cursor::Show)?;
+
+        theme.print_results(&mut stdout, self.msg, &path.to_string_lossy())?;
+
+        Ok(path)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    /// Takes a path, relative to the start directory
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<PathBuf> {
+        let answer = match answer {
+            Some(answer) if !answer.trim().is_empty() => answer.trim(),
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        let invalid = |err: String| Error::InvalidAnswer(self.msg.to_string(), err);
+        let path = fs::canonicalize(self.start.join(answer))
+            .map_err(|err| invalid(format!("{}: {}", answer, err)))?;
+        let dir = path.is_dir();
+        if self.dirs_only && !dir {
+            return Err(invalid(format!("{} is not a directory", answer)));
+        }
+        if !self.dirs_only && (dir || !self.accepts(&path, false)) {
+            return Err(invalid(format!(
+                "{} is not a file that can be picked",
+                answer
+            )));
+        }
+
+        print_answer(stdout, theme, self.msg, &path.to_string_lossy())?;
+        Ok(path)
+    }
+}
@@ -1,48 +1,98 @@
+use super::answers::AnswerSource;
+use super::derive::Prompt;
 use super::error::Result;
+use super::questions::{Answers, FromAnswers, Questions};
+use super::terminal::{StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
 use super::{Editor, IntoEditor};
-use std::io::{stdin, stdout, Read, Stdin, Stdout, Write};
-
-pub struct StdoutWrap(Stdout);
-
-impl Write for StdoutWrap {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.0.lock().write(buf)
-    }
-
-    fn flush(&mut self) -> std::io::Result<()> {
-        self.0.lock().flush()
-    }
-}
+use std::io::Read;
 
 pub struct Form<R, W> {
     theme: Theme,
     stdin: R,
     stdout: W,
+    answers: Option<Box<dyn AnswerSource>>,
 }
 
-impl<R: Read, W: Write> Form<R, W> {
+impl<R: Read, W: Terminal> Form<R, W> {
+    /// Ask on `stdout`, reading keys or lines from `stdin`.
+    ///
+    /// To ask on the process' stdout, read the process' stdin through
+    /// `StdinWrap`, not `std::io::stdin()`. Waiting for keys reads them
+    /// ahead into `StdinWrap`, see `Terminal::poll_input`.
     pub fn new(stdin: R, stdout: W, theme: Theme) -> Form<R, W> {
         Form {
             stdin,
             stdout,
             theme,
+            answers: None,
         }
     }
 
+    /// Answer named prompts from `answers` instead of asking the user
+    pub fn answers<A: AnswerSource + 'static>(mut self, answers: A) -> Form<R, W> {
+        self.answers = Some(Box::new(answers));
+        self
+    }
+
+    /// Whether the editors are talking to a user or to a pipe
+    pub fn is_interactive(&self) -> bool {
+        self.stdout.is_interactive()
+    }
+
     pub fn run<E: IntoEditor>(&mut self, editor: E) -> Result<<E::Editor as Editor>::Output> {
-        editor
-            .into_editor()
-            .run(&mut self.stdin, &mut self.stdout, &self.theme)
+        let editor = editor.into_editor();
+
+        let answer = match (&self.answers, editor.name()) {
+            (Some(answers), Some(name)) => answers.answer(name),
+            _ => None,
+        };
+
+        if let Some(answer) = answer {
+            return editor.answer(Some(&answer), &mut self.stdout, &self.theme);
+        }
+
+        editor.run(&mut self.stdin, &mut self.stdout, &self.theme)
+    }
+
+    /// Ask the questions in order, skipping those whose `when` condition
+    /// does not hold for the answers given before them
+    pub fn ask(&mut self, questions: &Questions) -> Result<Answers> {
+        let mut answers = Answers::new();
+
+        for question in &questions.questions {
+            if let Some(when) = &question.when {
+                if !when(&answers) {
+                    continue;
+                }
+            }
+
+            let answer = self.run(question)?;
+            answers.insert(&question.name, answer);
+        }
+
+        Ok(answers)
+    }
+
+    /// Fill in a `T` by asking for each of its fields
+    pub fn prompt<T: Prompt>(&mut self) -> Result<T> {
+        T::prompt(self)
+    }
+
+    /// Ask the questions and build a `T` from the answers
+    pub fn ask_as<T: FromAnswers>(&mut self, questions: &Questions) -> Result<T> {
+        let answers = self.ask(questions)?;
+        T::from_answers(&answers)
     }
 }
 
-impl Default for Form<Stdin, StdoutWrap> {
+impl Default for Form<StdinWrap, StdoutWrap> {
     fn default() -> Self {
         Form {
             theme: DEFAULT_THEME.clone(),
-            stdin: stdin(),
-            stdout: StdoutWrap(stdout()),
+            stdin: StdinWrap::new(),
+            stdout: StdoutWrap::new(),
+            answers: None,
         }
     }
 }
@@ -0,0 +1,98 @@
+/// Result of a successful fuzzy match
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Char indices in the text that matched the pattern
+    pub indices: Vec<usize>,
+}
+
+/// Match `pattern` as a case insensitive subsequence of `text`.
+///
+/// Consecutive matches and matches at the start of a word are rewarded,
+/// gaps between matched characters are penalized.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut indices = Vec::new();
+    let mut score: i64 = 0;
+    let mut prev: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+
+    for (i, c) in text.chars().enumerate() {
+        let p = match pattern.peek() {
+            Some(p) => *p,
+            None => break,
+        };
+
+        if c.to_lowercase().eq(std::iter::once(p)) {
+            score += 1;
+            match last_match {
+                Some(last) if last + 1 == i => score += 5,
+                Some(last) => score -= (i - last - 1) as i64,
+                None => score -= i as i64,
+            }
+            if prev.map(|p| !p.is_alphanumeric()).unwrap_or(true) {
+                score += 3;
+            }
+            indices.push(i);
+            last_match = Some(i);
+            pattern.next();
+        }
+        prev = Some(c);
+    }
+
+    if pattern.peek().is_some() {
+        return None;
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_case_insensitive_subsequence() {
+        let m = fuzzy_match("BRC", "broccoli").unwrap();
+        assert_eq!(m.indices, vec![0, 1, 3]);
+        assert!(fuzzy_match("cb", "broccoli").is_none());
+        assert!(fuzzy_match("brocx", "broccoli").is_none());
+    }
+
+    #[test]
+    fn empty_pattern_matches_everything() {
+        let m = fuzzy_match("", "anything").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.indices.is_empty());
+    }
+
+    #[test]
+    fn indices_are_chars_not_bytes() {
+        let m = fuzzy_match("rd", "crème brûlée dessert").unwrap();
+        assert_eq!(m.indices, vec![1, 13]);
+    }
+
+    #[test]
+    fn rewards_consecutive_matches() {
+        let consecutive = fuzzy_match("ban", "banana").unwrap();
+        let scattered = fuzzy_match("ban", "bean sprout").unwrap();
+        assert!(consecutive.score > scattered.score);
+    }
+
+    #[test]
+    fn rewards_word_starts() {
+        let word = fuzzy_match("s", "ice sorbet").unwrap();
+        let inner = fuzzy_match("s", "mousse").unwrap();
+        assert_eq!(word.indices, vec![4]);
+        assert!(word.score > inner.score);
+    }
+
+    #[test]
+    fn penalizes_late_first_match() {
+        let early = fuzzy_match("a", "apple").unwrap();
+        let late = fuzzy_match("a", "banana").unwrap();
+        assert_eq!(early.score, 4);
+        assert_eq!(late.score, 0);
+    }
+}
@@ -0,0 +1,137 @@
+use super::completer::Suggester;
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_LIMIT: usize = 500;
+
+/// Earlier answers to an `Input`, recalled with Up/Down and Ctrl-R.
+///
+/// Entries are deduplicated, so answering with an earlier entry moves it to
+/// the end. When a file is given, it is rewritten each time an entry is added.
+#[derive(Debug)]
+pub struct History {
+    entries: RefCell<Vec<String>>,
+    limit: usize,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// A history that only lives in memory
+    pub fn new() -> History {
+        History {
+            entries: RefCell::new(Vec::new()),
+            limit: DEFAULT_LIMIT,
+            path: None,
+        }
+    }
+
+    /// A history stored in `path`, one entry per line. The file is created
+    /// when the first entry is added.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<History> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+
+        let history = History {
+            entries: RefCell::new(Vec::new()),
+            limit: DEFAULT_LIMIT,
+            path: Some(path),
+        };
+        for entry in entries {
+            history.push(entry);
+        }
+        Ok(history)
+    }
+
+    /// Keep at most `limit` entries, dropping the oldest
+    pub fn limit(mut self, limit: usize) -> History {
+        self.limit = limit;
+        self.truncate();
+        self
+    }
+
+    /// Add an entry and save the history if it is file-backed. Empty and
+    /// multi-line entries are ignored.
+    pub fn add(&self, entry: &str) -> io::Result<()> {
+        if entry.is_empty() || entry.contains('\n') {
+            return Ok(());
+        }
+        self.push(entry.to_string());
+        self.save()
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> Vec<String> {
+        self.entries.borrow().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<String> {
+        self.entries.borrow().get(idx).cloned()
+    }
+
+    /// Index of the newest entry before `before` that contains `query`
+    pub(crate) fn search(&self, query: &str, before: usize) -> Option<usize> {
+        let entries = self.entries.borrow();
+        entries[..before.min(entries.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    fn push(&self, entry: String) {
+        self.entries.borrow_mut().retain(|e| *e != entry);
+        self.entries.borrow_mut().push(entry);
+        self.truncate();
+    }
+
+    fn truncate(&self) {
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() > self.limit {
+            let excess = entries.len() - self.limit;
+            entries.drain(..excess);
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            let mut content = self.entries.borrow().join("\n");
+            content.push('\n');
+            fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+/// Suggests the newest entry that starts with the input
+impl Suggester for History {
+    fn suggest(&self, input: &str) -> Option<String> {
+        self.entries
+            .borrow()
+            .iter()
+            .rev()
+            .find(|entry| entry.starts_with(input))
+            .cloned()
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new()
+    }
+}
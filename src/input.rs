@@ -1,33 +1,52 @@
-use super::editor::{Editor, IntoEditor};
+use super::completer::{Completer, Suggester};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
 use super::error::{Error, Result};
+use super::history::History;
+use super::line::{LineBuffer, LinePrompt};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
-use std::io::{stdin, stdout, Read, Write};
-use termion::input::TermRead;
-use termion::{
-    clear,
-    cursor::{self},
-};
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use termion::{event::Key, input::TermRead};
 use valid::{self, Valid, Validation};
 
 pub type Validator = Valid<Box<dyn Validation<String>>, String>;
 
-pub struct InputBuilder<'a> {
+/// Turns the typed text into the output of an `Input`
+pub type Parser<'a, T> = Box<dyn Fn(&str) -> std::result::Result<T, String> + 'a>;
+
+pub struct InputBuilder<'a, T = String> {
     msg: &'a str,
+    name: Option<&'a str>,
     default: Option<&'a str>,
     theme: Option<Theme>,
+    history: Option<&'a History>,
+    completer: Option<Box<dyn Completer + 'a>>,
+    suggester: Option<Box<dyn Suggester + 'a>>,
+    page_size: usize,
     validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+    parser: Parser<'a, T>,
 }
 
 impl<'a> InputBuilder<'a> {
     pub fn new(msg: &'a str) -> InputBuilder<'a> {
         InputBuilder {
             msg,
+            name: None,
             theme: None,
             default: None,
+            history: None,
+            completer: None,
+            suggester: None,
+            page_size: 8,
             validations: None,
+            parser: Box::new(|input| Ok(input.to_owned())),
         }
     }
+}
 
+impl<'a, T> InputBuilder<'a, T> {
     pub fn validate<V: Validation<String> + 'static>(mut self, v: V) -> Self {
         if let Some(valid) = self.validations.take() {
             self.validations = Some(valid.and_boxed(v));
@@ -46,118 +65,440 @@ impl<'a> InputBuilder<'a> {
         self
     }
 
-    pub fn theme(mut self, theme: Theme) -> InputBuilder<'a> {
+    pub fn theme(mut self, theme: Theme) -> InputBuilder<'a, T> {
         self.theme = Some(theme);
         self
     }
 
-    pub fn default(mut self, name: &'a str) -> InputBuilder<'a> {
+    pub fn default(mut self, name: &'a str) -> InputBuilder<'a, T> {
         self.default = Some(name);
         self
     }
 
-    pub fn build(self) -> Input<'a> {
+    /// Recall earlier answers with Up/Down and Ctrl-R, and add the answer
+    /// to `history`. Earlier answers are also suggested unless a suggester
+    /// is set.
+    pub fn history(mut self, history: &'a History) -> InputBuilder<'a, T> {
+        self.history = Some(history);
+        self
+    }
+
+    /// Offer completions from `completer` on Tab
+    pub fn completer(mut self, completer: impl Completer + 'a) -> InputBuilder<'a, T> {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Show a suggestion from `suggester` after the cursor, accepted with
+    /// Right or End, instead of from the history
+    pub fn suggester(mut self, suggester: impl Suggester + 'a) -> InputBuilder<'a, T> {
+        self.suggester = Some(Box::new(suggester));
+        self
+    }
+
+    /// Number of completions shown at once
+    pub fn page_size(mut self, size: usize) -> InputBuilder<'a, T> {
+        self.page_size = std::cmp::max(size, 1);
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> InputBuilder<'a, T> {
+        self.name = Some(name);
+        self
+    }
+
+    /// Parse the answer into a `U` with `FromStr`. Validations still run
+    /// on the typed text, before parsing.
+    pub fn parse<U>(self) -> InputBuilder<'a, U>
+    where
+        U: FromStr,
+        U::Err: Display,
+    {
+        self.parser(|input| input.parse::<U>())
+    }
+
+    /// Parse the answer into a `U` with `parser`. Validations still run on
+    /// the typed text, before parsing.
+    pub fn parser<U, E, F>(self, parser: F) -> InputBuilder<'a, U>
+    where
+        E: Display,
+        F: Fn(&str) -> std::result::Result<U, E> + 'a,
+    {
+        InputBuilder {
+            msg: self.msg,
+            name: self.name,
+            default: self.default,
+            theme: self.theme,
+            history: self.history,
+            completer: self.completer,
+            suggester: self.suggester,
+            page_size: self.page_size,
+            validations: self.validations,
+            parser: Box::new(move |input| parser(input).map_err(|e| e.to_string())),
+        }
+    }
+
+    pub fn build(self) -> Input<'a, T> {
         Input {
             msg: self.msg,
+            name: self.name,
             theme: self.theme,
             default: self.default,
+            history: self.history,
+            completer: self.completer,
+            suggester: self.suggester,
+            page_size: self.page_size,
             validations: self.validations,
+            parser: self.parser,
         }
     }
 }
 
-impl<'de> IntoEditor for InputBuilder<'de> {
-    type Editor = Input<'de>;
+impl<'de, T> IntoEditor for InputBuilder<'de, T> {
+    type Editor = Input<'de, T>;
     fn into_editor(self) -> Self::Editor {
         self.build()
     }
 }
 
-pub struct Input<'a> {
+pub struct Input<'a, T = String> {
     msg: &'a str,
+    name: Option<&'a str>,
     theme: Option<Theme>,
     default: Option<&'a str>,
+    history: Option<&'a History>,
+    completer: Option<Box<dyn Completer + 'a>>,
+    suggester: Option<Box<dyn Suggester + 'a>>,
+    page_size: usize,
     validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+    parser: Parser<'a, T>,
 }
 
 impl<'a> Input<'a> {
     pub fn new(msg: &'a str) -> InputBuilder<'a> {
         InputBuilder::new(msg)
     }
+}
 
-    pub fn run(&self) -> Result<String> {
-        <Input as Editor>::run(
+impl<'a, T> Input<'a, T> {
+    pub fn run(&self) -> Result<T> {
+        <Input<T> as Editor>::run(
             self,
-            &mut stdin(),
-            &mut stdout(),
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
             self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
         )
     }
 }
 
-impl<'a> Editor for Input<'a> {
-    type Output = String;
-    fn run<R: Read, W: Write>(
+/// State of a Ctrl-R reverse history search
+struct Search {
+    query: String,
+    /// Index of the history entry found
+    found: Option<usize>,
+}
+
+/// Candidates offered by the completer, cycled through with Tab
+struct Completion {
+    candidates: Vec<String>,
+    cur: usize,
+    offset: usize,
+    /// The line before completing, restored by Esc
+    original: String,
+}
+
+/// Everything that changes while an `Input` runs
+#[derive(Default)]
+struct State {
+    line: LineBuffer,
+    error: Option<String>,
+    /// While walking the history with Up/Down, the entry shown and the
+    /// line as it was typed
+    recall: Option<usize>,
+    typed: String,
+    search: Option<Search>,
+    completion: Option<Completion>,
+    prompt: LinePrompt,
+}
+
+impl<'a, T> Input<'a, T> {
+    fn render<W: Terminal>(&self, stdout: &mut W, theme: &Theme, state: &mut State) -> Result<()> {
+        let mut head = Vec::new();
+        let mut tail = Vec::new();
+        theme.print_question(&mut head, self.msg, self.default)?;
+        match (&state.search, self.history) {
+            (Some(search), Some(history)) => {
+                let entry = search.found.and_then(|idx| history.get(idx));
+                theme.print_history_search(&mut head, &search.query, entry.as_deref())?;
+            }
+            _ => {
+                let line = &state.line;
+                write!(head, "{}", &line.text()[..line.cursor()])?;
+                write!(tail, "{}", &line.text()[line.cursor()..])?;
+                if let Some(rest) = self.suggestion(state) {
+                    theme.print_suggestion(&mut tail, &rest)?;
+                }
+            }
+        }
+
+        let mut below = Vec::new();
+        write!(below, "\r\n")?;
+        if let Some(error) = &state.error {
+            theme.print_error(&mut below, error)?;
+        }
+        if let Some(completion) = &state.completion {
+            let visible = completion
+                .candidates
+                .iter()
+                .enumerate()
+                .skip(completion.offset)
+                .take(self.page_size);
+            for (i, candidate) in visible {
+                write!(below, "\r\n")?;
+                theme.print_choice(&mut below, candidate, i == completion.cur)?;
+            }
+        }
+
+        state.prompt.draw(stdout, &head, &tail, &below)?;
+        Ok(())
+    }
+
+    /// Validate and parse the answer
+    fn check(&self, input: &str) -> std::result::Result<T, String> {
+        if let Some(v) = &self.validations {
+            v.validate(&input.to_owned()).map_err(|e| e.to_string())?;
+        }
+        (self.parser)(input)
+    }
+
+    /// The rest of the suggestion for the line, only offered while typing
+    /// at the end of it
+    fn suggestion(&self, state: &State) -> Option<String> {
+        let line = &state.line;
+        if line.is_empty() || !line.at_end() || state.completion.is_some() {
+            return None;
+        }
+
+        let suggestion = match (&self.suggester, self.history) {
+            (Some(suggester), _) => suggester.suggest(line.text()),
+            (None, Some(history)) => history.suggest(line.text()),
+            (None, None) => None,
+        };
+        suggestion
+            .filter(|s| s.len() > line.text().len() && s.starts_with(line.text()))
+            .map(|s| s[line.text().len()..].to_owned())
+    }
+
+    /// Handle a key while searching the history, returning false when the
+    /// search is done and the key should be handled as usual
+    fn search_key(&self, state: &mut State, key: Key) -> bool {
+        let (mut search, history) = match (state.search.take(), self.history) {
+            (Some(search), Some(history)) => (search, history),
+            _ => return false,
+        };
+
+        match key {
+            Key::Ctrl('r') => {
+                let before = search.found.unwrap_or_else(|| history.len());
+                search.found = history.search(&search.query, before).or(search.found);
+            }
+            Key::Char(c) if !c.is_control() => {
+                search.query.push(c);
+                let before = search.found.map(|idx| idx + 1);
+                search.found =
+                    history.search(&search.query, before.unwrap_or_else(|| history.len()));
+            }
+            Key::Backspace => {
+                search.query.pop();
+                search.found = history.search(&search.query, history.len());
+            }
+            Key::Esc | Key::Ctrl('g') => return true,
+            _ => {
+                // Any other key accepts the entry found
+                if let Some(entry) = search.found.and_then(|idx| history.get(idx)) {
+                    state.line.set(&entry);
+                }
+                return false;
+            }
+        }
+
+        state.search = Some(search);
+        true
+    }
+
+    /// Handle a key while completing, returning false when the key should
+    /// be handled as usual
+    fn complete_key(&self, state: &mut State, key: Key) -> bool {
+        let completer = match &self.completer {
+            Some(completer) => completer,
+            None => return false,
+        };
+
+        match (key, state.completion.take()) {
+            (Key::Char('\t'), Some(mut completion)) => {
+                completion.cur = (completion.cur + 1) % completion.candidates.len();
+                if completion.cur < completion.offset {
+                    completion.offset = completion.cur;
+                } else if completion.cur >= completion.offset + self.page_size {
+                    completion.offset = completion.cur + 1 - self.page_size;
+                }
+                state.line.set(&completion.candidates[completion.cur]);
+                state.completion = Some(completion);
+            }
+            (Key::Char('\t'), None) => {
+                let candidates = completer.complete(state.line.text());
+                match candidates.len() {
+                    0 => {}
+                    1 => state.line.set(&candidates[0]),
+                    _ => {
+                        let original = state.line.text().to_owned();
+                        state.line.set(&candidates[0]);
+                        state.completion = Some(Completion {
+                            candidates,
+                            cur: 0,
+                            offset: 0,
+                            original,
+                        });
+                    }
+                }
+            }
+            (Key::Esc, Some(completion)) => state.line.set(&completion.original),
+            // Enter takes the candidate without submitting
+            (Key::Char('\n'), Some(_)) => {}
+            _ => return false,
+        }
+
+        state.error = None;
+        true
+    }
+}
+
+impl<'a, T> Editor for Input<'a, T> {
+    type Output = T;
+    fn run<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
     ) -> Result<Self::Output> {
-        //let mut input = String::default();
-
-        let mut error: Option<String> = None;
-
-        let input = 'ui: loop {
-            let w = theme.print_question(stdout, self.msg, self.default)?;
-            if let Some(error) = &error {
-                let dir = if error.len() < w {
-                    cursor::Right((w - error.len() - 2) as u16).to_string()
-                } else {
-                    cursor::Left((error.len() - w + 2) as u16).to_string()
-                };
-                write!(stdout, "\r\n")?;
-                theme.print_error(stdout, &error)?;
-                write!(stdout, "{}{}", cursor::Up(1), dir)?;
-            } else {
-                write!(stdout, "\n{}{}", cursor::Up(1), cursor::Right(w as u16))?;
-            }
-            stdout.flush()?;
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
 
-            let mut input = match TermRead::read_line(stdin) {
-                Ok(Some(s)) => s,
-                Ok(None) => return Err(Error::NoMoreInput),
-                Err(e) => return Err(Error::IoError(e)),
-            };
-            if input.is_empty() {
-                if let Some(default) = self.default {
-                    input = default.to_owned();
-                    break 'ui input;
-                }
-            }
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let mut state = State::default();
 
-            error = if let Some(v) = &self.validations {
-                if let Err(err) = v.validate(&input) {
-                    Some(err.to_string())
-                } else {
-                    None
-                }
-            } else {
-                None
+        let (input, value) = loop {
+            self.render(&mut stdout, theme, &mut state)?;
+
+            let key = match keys.next() {
+                Some(key) => key?,
+                None => return Err(Error::NoMoreInput),
             };
 
-            if error.is_none() {
-                break 'ui input;
+            if self.search_key(&mut state, key) || self.complete_key(&mut state, key) {
+                continue;
             }
 
-            write!(stdout, "\r{}", cursor::Up(1))?;
+            match (key, self.history) {
+                (Key::Char('\n'), _) => {
+                    let input = match (state.line.is_empty(), self.default) {
+                        (true, Some(default)) => default.to_owned(),
+                        _ => state.line.text().to_owned(),
+                    };
+
+                    match self.check(&input) {
+                        Ok(value) => break (input, value),
+                        Err(err) => state.error = Some(err),
+                    }
+                    state.line.clear();
+                    state.recall = None;
+                }
+                (Key::Ctrl('c'), _) => {
+                    write!(stdout, "\n\r")?;
+                    return Err(Error::UserAborted);
+                }
+                (Key::Ctrl('d'), _) if state.line.is_empty() => return Err(Error::NoMoreInput),
+                (Key::Up, Some(history)) => {
+                    let idx = match state.recall {
+                        Some(idx) => idx.saturating_sub(1),
+                        None if history.is_empty() => continue,
+                        None => {
+                            state.typed = state.line.text().to_owned();
+                            history.len() - 1
+                        }
+                    };
+                    if let Some(entry) = history.get(idx) {
+                        state.line.set(&entry);
+                        state.recall = Some(idx);
+                    }
+                }
+                (Key::Down, Some(history)) => match state.recall {
+                    Some(idx) if idx + 1 < history.len() => {
+                        if let Some(entry) = history.get(idx + 1) {
+                            state.line.set(&entry);
+                        }
+                        state.recall = Some(idx + 1);
+                    }
+                    Some(_) => {
+                        state.line.set(&state.typed);
+                        state.recall = None;
+                    }
+                    None => {}
+                },
+                (Key::Right, _) | (Key::End, _) | (Key::Ctrl('e'), _)
+                    if self.suggestion(&state).is_some() =>
+                {
+                    if let Some(rest) = self.suggestion(&state) {
+                        state.line.insert_str(&rest);
+                    }
+                }
+                (Key::Ctrl('r'), Some(_)) => {
+                    state.search = Some(Search {
+                        query: String::new(),
+                        found: None,
+                    });
+                }
+                (key, _) => {
+                    state.line.edit(key);
+                }
+            }
         };
 
-        if error.is_some() {
-            write!(stdout, "{}", clear::CurrentLine)?;
+        if let Some(history) = self.history {
+            // Failing to save the history is no reason to lose the answer
+            let _ = history.add(&input);
         }
-        write!(stdout, "{}", cursor::Up(1))?;
-        theme.print_results(stdout, self.msg, &input)?;
-        Ok(input)
+
+        // Clear a previous validation error and completions below the input
+        state.prompt.clear(&mut stdout)?;
+        theme.print_results(&mut stdout, self.msg, &input)?;
+        Ok(value)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<T> {
+        let input = match (answer, self.default) {
+            (Some(""), Some(default)) | (None, Some(default)) => default.to_owned(),
+            (Some(answer), _) => answer.to_owned(),
+            (None, None) => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        let value = self
+            .check(&input)
+            .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?;
+        print_answer(stdout, theme, self.msg, &input)?;
+        Ok(value)
     }
 }
 
@@ -1,20 +1,65 @@
+// Every editor's `new` starts its builder, like `Select::new`
+#![allow(clippy::new_ret_no_self)]
+
+mod answers;
+mod autocomplete;
 mod choice;
+mod completer;
 mod confirm;
+mod date;
+mod datepicker;
+mod derive;
 mod editor;
 pub mod error;
+mod expand;
+mod external;
+mod filepicker;
 mod form;
+mod fuzzy;
+mod history;
 mod input;
+mod line;
 mod multiselect;
+mod number;
 mod password;
+mod questions;
 mod select;
+mod slider;
+mod sort;
+mod terminal;
+pub mod testing;
+mod textarea;
 mod theme;
+mod tree;
 
+pub use self::answers::*;
+pub use self::autocomplete::*;
 pub use self::choice::*;
+pub use self::completer::*;
 pub use self::confirm::*;
+pub use self::date::*;
+pub use self::datepicker::*;
+pub use self::derive::*;
 pub use self::editor::*;
+pub use self::expand::*;
+pub use self::external::*;
+pub use self::filepicker::*;
 pub use self::form::Form;
+pub use self::history::History;
 pub use self::input::*;
 pub use self::multiselect::*;
+pub use self::number::*;
 pub use self::password::*;
+pub use self::questions::*;
 pub use self::select::*;
+pub use self::slider::*;
+pub use self::sort::*;
+pub use self::terminal::*;
+pub use self::textarea::*;
 pub use self::theme::*;
+pub use self::tree::*;
+
+#[cfg(feature = "derive")]
+pub use prompt_derive::Prompt;
+
+pub use valid;
@@ -0,0 +1,284 @@
+use super::terminal::Terminal;
+use std::io::{self, Write};
+use termion::{clear, cursor, event::Key};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Text being edited on a single line, with a cursor that always sits on a
+/// grapheme boundary
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct LineBuffer {
+    text: String,
+    /// Byte offset into `text`
+    cursor: usize,
+}
+
+impl LineBuffer {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Replace the text, moving the cursor to the end
+    pub fn set(&mut self, text: &str) {
+        self.text = text.to_owned();
+        self.cursor = self.text.len();
+    }
+
+    /// Byte offset of the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move the cursor to the byte offset `pos`, which must be on a
+    /// grapheme boundary
+    pub fn set_cursor(&mut self, pos: usize) {
+        self.cursor = std::cmp::min(pos, self.text.len());
+    }
+
+    /// Delete the bytes in `start..end`, keeping the cursor on the same text
+    pub fn delete_range(&mut self, start: usize, end: usize) {
+        self.text.replace_range(start..end, "");
+        if self.cursor >= end {
+            self.cursor -= end - start;
+        } else if self.cursor > start {
+            self.cursor = start;
+        }
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.cursor == self.text.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.text.insert_str(self.cursor, s);
+        self.cursor += s.len();
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.prev_boundary();
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    /// Delete the grapheme before the cursor
+    pub fn backspace(&mut self) {
+        let start = self.prev_boundary();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Delete the grapheme under the cursor
+    pub fn delete(&mut self) {
+        let end = self.next_boundary();
+        self.text.replace_range(self.cursor..end, "");
+    }
+
+    /// Delete the word before the cursor, along with any whitespace between
+    /// it and the cursor
+    pub fn delete_word(&mut self) {
+        let before = &self.text[..self.cursor];
+        let trimmed = before.trim_end();
+        let start = trimmed
+            .grapheme_indices(true)
+            .rev()
+            .find(|(_, g)| g.chars().all(char::is_whitespace))
+            .map(|(i, g)| i + g.len())
+            .unwrap_or(0);
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Delete everything before the cursor
+    pub fn delete_to_start(&mut self) {
+        self.text.replace_range(..self.cursor, "");
+        self.cursor = 0;
+    }
+
+    /// Delete everything from the cursor on
+    pub fn delete_to_end(&mut self) {
+        self.text.truncate(self.cursor);
+    }
+
+    /// Apply an editing key, returning false for keys that don't edit
+    pub fn edit(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char(c) if !c.is_control() => self.insert(c),
+            Key::Left | Key::Ctrl('b') => self.left(),
+            Key::Right | Key::Ctrl('f') => self.right(),
+            Key::Home | Key::Ctrl('a') => self.home(),
+            Key::End | Key::Ctrl('e') => self.end(),
+            Key::Backspace | Key::Ctrl('h') => self.backspace(),
+            Key::Delete => self.delete(),
+            Key::Ctrl('w') => self.delete_word(),
+            Key::Ctrl('u') => self.delete_to_start(),
+            Key::Ctrl('k') => self.delete_to_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map(|g| self.cursor + g.len())
+            .unwrap_or(self.cursor)
+    }
+}
+
+/// A row of wrapped text, as byte offsets into the text
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Row {
+    pub start: usize,
+    pub end: usize,
+    /// Whether this is the last row of a line
+    pub last: bool,
+}
+
+impl Row {
+    pub fn contains(&self, pos: usize) -> bool {
+        self.start <= pos && (pos < self.end || (pos == self.end && self.last))
+    }
+}
+
+/// Wrap `text` into rows at most `width` columns wide
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut offset = 0;
+    for line in text.split('\n') {
+        let mut row = Row {
+            start: offset,
+            end: offset,
+            last: false,
+        };
+        let mut w = 0;
+        for (i, g) in line.grapheme_indices(true) {
+            let gw = g.width();
+            if w + gw > width && w > 0 {
+                rows.push(row);
+                row = Row {
+                    start: offset + i,
+                    end: offset + i,
+                    last: false,
+                };
+                w = 0;
+            }
+            row.end = offset + i + g.len();
+            w += gw;
+        }
+
+        // A full row leaves no room for the cursor after it
+        if w >= width {
+            rows.push(row);
+            row = Row {
+                start: row.end,
+                end: row.end,
+                last: false,
+            };
+        }
+        row.last = true;
+        rows.push(row);
+        offset += line.len() + 1;
+    }
+    rows
+}
+
+/// Draws a question followed by the line being edited, wrapped to the
+/// terminal width, with rows of hints or errors below it
+#[derive(Debug, Default)]
+pub(crate) struct LinePrompt {
+    /// Row of the cursor below the first row, as last drawn
+    row: usize,
+}
+
+impl LinePrompt {
+    /// Redraw with the cursor between `head` and `tail`. `below` is drawn
+    /// under the last row, each of its rows starting with `\r\n`.
+    pub fn draw<W: Terminal>(
+        &mut self,
+        stdout: &mut W,
+        head: &[u8],
+        tail: &[u8],
+        below: &[u8],
+    ) -> io::Result<()> {
+        let width = std::cmp::max(stdout.size()?.0 as usize, 1);
+        let before = plain(head)?;
+        let text = before.clone() + &plain(tail)?;
+        let rows = wrap(&text, width);
+        let row = rows
+            .iter()
+            .position(|r| r.contains(before.len()))
+            .unwrap_or(0);
+        let col = text[rows[row].start..before.len()].width();
+        let last = rows.len() - 1;
+
+        self.clear(stdout)?;
+        stdout.write_all(head)?;
+        stdout.write_all(tail)?;
+        // The cursor stays on a full row until something is written, so
+        // move it to the empty row the wrap left for it
+        if last > 0 && rows[last].start == rows[last].end {
+            write!(stdout, "\r\n")?;
+        }
+        stdout.write_all(below)?;
+
+        let up = below.iter().filter(|b| **b == b'\n').count() + last - row;
+        if up > 0 {
+            write!(stdout, "{}", cursor::Up(up as u16))?;
+        }
+        write!(stdout, "\r")?;
+        if col > 0 {
+            write!(stdout, "{}", cursor::Right(col as u16))?;
+        }
+        self.row = row;
+        stdout.flush()
+    }
+
+    /// Move back to the first row and clear everything drawn
+    pub fn clear<W: Write>(&mut self, stdout: &mut W) -> io::Result<()> {
+        if self.row > 0 {
+            write!(stdout, "{}", cursor::Up(self.row as u16))?;
+        }
+        write!(stdout, "\r{}", clear::AfterCursor)?;
+        self.row = 0;
+        Ok(())
+    }
+}
+
+/// Text drawn by `output`, without the escape codes styling it
+fn plain(output: &[u8]) -> io::Result<String> {
+    let stripped = strip_ansi_escapes::strip(output)?;
+    Ok(String::from_utf8_lossy(&stripped).into_owned())
+}
@@ -1,110 +1,175 @@
-use super::choice::Choice;
-use super::editor::Editor;
+use super::choice::{find_selectable, is_selectable, Choice, Cursor, Selection};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
 use super::error::Error;
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
+use std::borrow::Cow;
 // use super::validation::{Validation, ValidationError};
-use std::collections::HashMap;
-use std::io::{stdin, stdout, Read, Write};
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
 use termion::event::Key;
 use termion::input::TermRead;
-use termion::raw::IntoRawMode;
 use termion::{clear, cursor};
 use valid::{Error as ValidationError, Validation};
 
-pub struct MultiSelectBuilder<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
+pub struct MultiSelectBuilder<'de, C: Clone> {
     msg: &'de str,
-    choices: &'de [C],
+    name: Option<&'de str>,
+    choices: Cow<'de, [C]>,
     page_size: usize,
+    selected: Vec<usize>,
     theme: Option<Theme>,
-    validations: Vec<Box<dyn Validation<Vec<&'de C>>>>,
+    validations: Vec<Box<dyn Validation<Vec<C>>>>,
 }
 
-impl<'de, C, V> MultiSelectBuilder<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    pub fn new(msg: &'de str, choices: &'de [C]) -> MultiSelectBuilder<'de, C, V> {
+impl<'de, C: Choice + Clone> MultiSelectBuilder<'de, C> {
+    pub fn new(msg: &'de str, choices: impl Into<Cow<'de, [C]>>) -> MultiSelectBuilder<'de, C> {
         MultiSelectBuilder {
             msg,
-            choices,
+            name: None,
+            choices: choices.into(),
             page_size: 8,
+            selected: Vec::new(),
             theme: None,
             validations: Vec::default(),
         }
     }
 
-    pub fn page_size(mut self, size: usize) -> MultiSelectBuilder<'de, C, V> {
-        self.page_size = size;
+    pub fn page_size(mut self, size: usize) -> MultiSelectBuilder<'de, C> {
+        self.page_size = std::cmp::max(size, 1);
+        self
+    }
+
+    /// Indices of the choices checked up front, and the answer when none
+    /// is given. Those out of range or not selectable are ignored.
+    pub fn selected<I: IntoIterator<Item = usize>>(
+        mut self,
+        indices: I,
+    ) -> MultiSelectBuilder<'de, C> {
+        let choices = &self.choices;
+        self.selected = indices
+            .into_iter()
+            .filter(|idx| matches!(choices.get(*idx), Some(c) if is_selectable(c)))
+            .collect();
+        self.selected.sort_unstable();
+        self.selected.dedup();
         self
     }
 
-    pub fn validate<VV: Validation<Vec<&'de C>> + 'static>(mut self, v: VV) -> Self {
+    /// Check the choices matching `predicate` up front, like `selected`
+    pub fn selected_by<F: Fn(&C) -> bool>(self, predicate: F) -> MultiSelectBuilder<'de, C> {
+        let indices = (0..self.choices.len())
+            .filter(|idx| predicate(&self.choices[*idx]))
+            .collect::<Vec<_>>();
+        self.selected(indices)
+    }
+
+    pub fn validate<VV: Validation<Vec<C>> + 'static>(mut self, v: VV) -> Self {
         self.validations.push(Box::new(v));
         self
     }
 
-    pub fn build(self) -> MultiSelect<'de, C, V> {
+    pub fn name(mut self, name: &'de str) -> MultiSelectBuilder<'de, C> {
+        self.name = Some(name);
+        self
+    }
+
+    /// Build a `MultiSelect` returning where the choices picked were in the
+    /// list, along with their values
+    pub fn indexed(self) -> IndexedMultiSelect<'de, C> {
+        IndexedMultiSelect {
+            multi: self.build(),
+        }
+    }
+
+    pub fn build(self) -> MultiSelect<'de, C> {
         MultiSelect {
             msg: self.msg,
+            name: self.name,
             choices: self.choices,
             page_size: self.page_size,
+            selected: self.selected,
             theme: self.theme,
             validations: self.validations,
         }
     }
 }
 
-pub struct MultiSelect<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
+impl<'de, C: Choice + Clone> IntoEditor for MultiSelectBuilder<'de, C> {
+    type Editor = MultiSelect<'de, C>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Checks any number of a list of choices, which can be borrowed or owned.
+/// The choices picked are returned as clones.
+pub struct MultiSelect<'de, C: Clone> {
     msg: &'de str,
-    choices: &'de [C],
+    name: Option<&'de str>,
+    choices: Cow<'de, [C]>,
     page_size: usize,
+    selected: Vec<usize>,
     theme: Option<Theme>,
-    validations: Vec<Box<dyn Validation<Vec<&'de C>>>>,
+    validations: Vec<Box<dyn Validation<Vec<C>>>>,
 }
 
-impl<'de, C, V> MultiSelect<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    pub fn new(msg: &'de str, choices: &'de [C]) -> MultiSelectBuilder<'de, C, V> {
+impl<'de, C: Choice + Clone> MultiSelect<'de, C> {
+    pub fn new(msg: &'de str, choices: impl Into<Cow<'de, [C]>>) -> MultiSelectBuilder<'de, C> {
         MultiSelectBuilder::new(msg, choices)
     }
 
-    pub fn run(&self) -> Result<Vec<&'de C>, Error> {
-        <MultiSelect<'de, C, V> as Editor>::run(
+    pub fn run(&self) -> Result<Vec<C>, Error> {
+        <MultiSelect<'de, C> as Editor>::run(
             self,
-            &mut stdin(),
-            &mut stdout(),
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
             self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
         )
     }
 
-    pub fn validate(&self, val: &Vec<&'de C>) -> Result<(), ValidationError> {
+    pub fn validate(&self, val: &Vec<C>) -> Result<(), ValidationError> {
         for v in &self.validations {
             v.validate(val)?;
         }
         Ok(())
     }
-}
 
-impl<'de, C, V> Editor for MultiSelect<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    type Output = Vec<&'de C>;
-    fn run<R: Read, W: Write>(
+    fn choices(&self, indices: &[usize]) -> Vec<C> {
+        indices
+            .iter()
+            .map(|idx| self.choices[*idx].clone())
+            .collect()
+    }
+
+    fn summary(&self, indices: &[usize]) -> String {
+        indices
+            .iter()
+            .map(|idx| self.choices[*idx].text().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Indices of the choices picked, in order. `validate` checks them
+    /// before they are accepted.
+    fn pick<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
-    ) -> Result<Self::Output, Error> {
-        let mut stdout = stdout.into_raw_mode()?;
+        validate: &dyn Fn(&[usize]) -> Result<(), ValidationError>,
+    ) -> Result<Vec<usize>, Error> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer_indices(answer.as_deref(), stdout, theme, validate);
+        }
+
+        // An empty list has nothing to pick
+        if self.choices.is_empty() {
+            return Err(Error::NoAnswer(self.msg.to_string()));
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
 
         theme.print_question(&mut stdout, self.msg, None)?;
         write!(stdout, "\n{}", cursor::Hide)?;
@@ -115,27 +180,33 @@ where
             write!(stdout, "\n")?;
         }
 
-        let mut cur: usize = 0;
-        let mut offset: usize = 0;
+        let selectable = |i: usize| is_selectable(&self.choices[i]);
+        // Start on the first choice picked up front
+        let start = self.selected.first().copied().unwrap_or(0);
+        let mut cursor = Cursor::new(self.choices.len(), rows, start, selectable);
 
         let mut input = stdin.keys();
 
-        let mut choices: HashMap<usize, &C> = HashMap::default();
+        let mut selected: BTreeSet<usize> = self.selected.iter().copied().collect();
 
         let mut error: Option<String> = None;
 
         loop {
             write!(stdout, "{}", cursor::Up((rows + 0) as u16))?;
-            let cur_idx = offset + cur;
+            let (cur, offset) = (cursor.row(), cursor.offset);
 
             for (i, s) in self.choices.iter().skip(offset).take(rows).enumerate() {
                 write!(stdout, "\n\r{}", clear::CurrentLine)?;
-                theme.print_multiple_choice(
-                    &mut stdout,
-                    s,
-                    cur == i,
-                    choices.contains_key(&(offset + i)),
-                )?;
+                if !is_selectable(s) {
+                    theme.print_unselectable(&mut stdout, s, true)?;
+                } else {
+                    theme.print_multiple_choice(
+                        &mut stdout,
+                        s,
+                        cur == i,
+                        selected.contains(&(offset + i)),
+                    )?;
+                }
             }
 
             if let Some(error) = &error {
@@ -148,36 +219,29 @@ where
 
             stdout.flush()?;
 
-            let next = input.next().unwrap();
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
 
-            match next? {
+            match next {
                 // Enter
                 Key::Char('\n') => {
-                    let choices = choices.iter().map(|m| *m.1).collect::<Vec<_>>();
-                    match self.validate(&choices) {
+                    let indices = selected.iter().copied().collect::<Vec<_>>();
+                    match validate(&indices) {
                         Ok(_) => break,
                         Err(err) => {
                             error = Some(err.to_string());
                         }
                     }
                 }
-                Key::Up if cur != 0 => {
-                    cur -= 1;
-                }
-                Key::Up if cur == 0 && offset > 0 => {
-                    offset -= 1;
-                }
-                Key::Down if cur != rows - 1 => {
-                    cur += 1;
-                }
-                Key::Down if (cur == rows - 1) && offset < (self.choices.len() - rows) => {
-                    offset += 1;
-                }
-                Key::Char(' ') => {
-                    if choices.contains_key(&cur_idx) {
-                        choices.remove(&cur_idx);
+                Key::Up => cursor.up(selectable),
+                Key::Down => cursor.down(selectable),
+                Key::Char(' ') if selectable(cursor.pos) => {
+                    if selected.contains(&cursor.pos) {
+                        selected.remove(&cursor.pos);
                     } else {
-                        choices.insert(cur_idx, &self.choices[cur_idx]);
+                        selected.insert(cursor.pos);
                     }
                 }
                 Key::Ctrl('c') => {
@@ -187,39 +251,157 @@ where
                 _ => {}
             }
         }
-        // Clear choices
+        // Clear error and choices
+        write!(stdout, "{}{}", cursor::Down(1), clear::CurrentLine)?;
         for _ in 0..(rows + 1) {
-            write!(stdout, "{}{}", clear::CurrentLine, cursor::Up(1))?;
+            write!(stdout, "{}{}", cursor::Up(1), clear::CurrentLine)?;
         }
 
-        write!(stdout, "\n\r{}", cursor::Show)?;
+        write!(stdout, "\r{}", cursor::Show)?;
 
-        let mut choices = choices.iter().map(|m| *m.0).collect::<Vec<_>>();
-        choices.sort();
+        let indices = selected.into_iter().collect::<Vec<_>>();
 
-        theme.print_results(
-            &mut stdout,
-            self.msg,
-            choices
-                .iter()
-                .map(|m| self.choices[*m].text().to_string())
-                .collect::<Vec<_>>()
-                .join(", ")
-                .as_str(),
-        )?;
+        theme.print_results(&mut stdout, self.msg, &self.summary(&indices))?;
 
-        let choices = choices
-            .iter()
-            .map(|m| &self.choices[*m])
-            .collect::<Vec<_>>();
+        Ok(indices)
+    }
 
-        Ok(choices)
+    fn answer_indices<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+        validate: &dyn Fn(&[usize]) -> Result<(), ValidationError>,
+    ) -> Result<Vec<usize>, Error> {
+        // An empty line is no answer, not an empty selection
+        let answer = answer.filter(|answer| !answer.trim().is_empty());
+
+        let indices = match answer {
+            Some(answer) => {
+                let mut indices = Vec::new();
+                for part in answer.split(',').filter(|p| !p.trim().is_empty()) {
+                    let idx = find_selectable(self.msg, &self.choices, part)?;
+                    if !indices.contains(&idx) {
+                        indices.push(idx);
+                    }
+                }
+                indices.sort();
+                indices
+            }
+            None if !self.selected.is_empty() => self.selected.clone(),
+            None => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        if let Err(err) = validate(&indices) {
+            return Err(Error::InvalidAnswer(self.msg.to_string(), err.to_string()));
+        }
+
+        print_answer(stdout, theme, self.msg, &self.summary(&indices))?;
+
+        Ok(indices)
+    }
+}
+
+impl<'de, C: Choice + Clone> Editor for MultiSelect<'de, C> {
+    type Output = Vec<C>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output, Error> {
+        let validate = |indices: &[usize]| self.validate(&self.choices(indices));
+        self.pick(stdin, stdout, theme, &validate)
+            .map(|indices| self.choices(&indices))
     }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Vec<C>, Error> {
+        let validate = |indices: &[usize]| self.validate(&self.choices(indices));
+        self.answer_indices(answer, stdout, theme, &validate)
+            .map(|indices| self.choices(&indices))
+    }
+}
+
+pub fn multi_select<'de, C: Choice + Clone + 'de>(
+    msg: &'de str,
+    choices: impl Into<Cow<'de, [C]>>,
+) -> Result<Vec<C>, Error> {
+    MultiSelectBuilder::new(msg, choices).build().run()
+}
+
+/// A `MultiSelect` returning the indices and the values of the choices
+/// picked
+pub struct IndexedMultiSelect<'de, C: Clone> {
+    multi: MultiSelect<'de, C>,
 }
 
-pub fn multi_select<'de, C, V>(msg: &'de str, choices: &'de [C]) -> Result<Vec<&'de C>, Error>
+impl<'de, C> IndexedMultiSelect<'de, C>
 where
-    C: Choice<Value = V>,
+    C: Choice + Clone,
+    C::Value: Clone,
 {
-    MultiSelectBuilder::new(msg, choices).build().run()
+    pub fn run(&self) -> Result<Vec<Selection<C::Value>>, Error> {
+        <IndexedMultiSelect<'de, C> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.multi.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn selections(&self, indices: Vec<usize>) -> Vec<Selection<C::Value>> {
+        indices
+            .into_iter()
+            .map(|index| Selection {
+                index,
+                value: self.multi.choices[index].value().clone(),
+            })
+            .collect()
+    }
+}
+
+impl<'de, C> Editor for IndexedMultiSelect<'de, C>
+where
+    C: Choice + Clone,
+    C::Value: Clone,
+{
+    type Output = Vec<Selection<C::Value>>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output, Error> {
+        let validate = |indices: &[usize]| self.multi.validate(&self.multi.choices(indices));
+        self.multi
+            .pick(stdin, stdout, theme, &validate)
+            .map(|indices| self.selections(indices))
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.multi.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output, Error> {
+        let validate = |indices: &[usize]| self.multi.validate(&self.multi.choices(indices));
+        self.multi
+            .answer_indices(answer, stdout, theme, &validate)
+            .map(|indices| self.selections(indices))
+    }
 }
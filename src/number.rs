@@ -0,0 +1,301 @@
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::line::{LineBuffer, LinePrompt};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use termion::{event::Key, input::TermRead};
+
+/// Numbers a `Number` prompt can ask for
+pub trait Numeric: FromStr + Display + PartialOrd + Copy {
+    /// Default step for Up/Down
+    fn one() -> Self;
+    fn zero() -> Self;
+    /// `self + step`, saturating at the type's bounds
+    fn step_up(self, step: Self) -> Self;
+    /// `self - step`, saturating at the type's bounds
+    fn step_down(self, step: Self) -> Self;
+    /// Lossy conversion, for drawing the number
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! int_numeric {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            fn one() -> Self { 1 }
+            fn zero() -> Self { 0 }
+            fn step_up(self, step: Self) -> Self { self.saturating_add(step) }
+            fn step_down(self, step: Self) -> Self { self.saturating_sub(step) }
+            fn to_f64(self) -> f64 { self as f64 }
+        }
+    )*};
+}
+
+macro_rules! float_numeric {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            fn one() -> Self { 1.0 }
+            fn zero() -> Self { 0.0 }
+            fn step_up(self, step: Self) -> Self { self + step }
+            fn step_down(self, step: Self) -> Self { self - step }
+            fn to_f64(self) -> f64 { self as f64 }
+        }
+    )*};
+}
+
+int_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_numeric!(f32, f64);
+
+/// Parse a number, NaN is not one
+pub(crate) fn parse_number<T: Numeric>(input: &str) -> Option<T> {
+    input
+        .parse::<T>()
+        .ok()
+        .filter(|n| n.partial_cmp(n).is_some())
+}
+
+pub type Int<'a> = Number<'a, i64>;
+pub type Float<'a> = Number<'a, f64>;
+
+pub struct NumberBuilder<'a, T: Numeric> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<T>,
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+}
+
+impl<'a, T: Numeric> NumberBuilder<'a, T> {
+    pub fn new(msg: &'a str) -> NumberBuilder<'a, T> {
+        NumberBuilder {
+            msg,
+            name: None,
+            theme: None,
+            default: None,
+            min: None,
+            max: None,
+            step: T::one(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> NumberBuilder<'a, T> {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn default(mut self, default: T) -> NumberBuilder<'a, T> {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn min(mut self, min: T) -> NumberBuilder<'a, T> {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: T) -> NumberBuilder<'a, T> {
+        self.max = Some(max);
+        self
+    }
+
+    /// Amount Up/Down change the number by
+    pub fn step(mut self, step: T) -> NumberBuilder<'a, T> {
+        self.step = step;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> NumberBuilder<'a, T> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Number<'a, T> {
+        Number {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            default: self.default.map(|d| d.to_string()),
+            min: self.min,
+            max: self.max,
+            step: self.step,
+        }
+    }
+}
+
+impl<'a, T: Numeric> IntoEditor for NumberBuilder<'a, T> {
+    type Editor = Number<'a, T>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Asks for a number, which can also be stepped with Up/Down
+pub struct Number<'a, T: Numeric> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<String>,
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+}
+
+impl<'a, T: Numeric> Number<'a, T> {
+    pub fn new(msg: &'a str) -> NumberBuilder<'a, T> {
+        NumberBuilder::new(msg)
+    }
+
+    pub fn run(&self) -> Result<T> {
+        <Number<T> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    /// Parse and range check `input`, an empty input gives the default
+    fn parse(&self, input: &str) -> std::result::Result<T, String> {
+        let input = match (input.trim(), &self.default) {
+            ("", Some(default)) => default.as_str(),
+            (input, _) => input,
+        };
+
+        let n =
+            parse_number::<T>(input).ok_or_else(|| format!("{:?} is not a valid number", input))?;
+        match (self.min, self.max) {
+            (Some(min), _) if n < min => Err(format!("Must be at least {}", min)),
+            (_, Some(max)) if n > max => Err(format!("Must be at most {}", max)),
+            _ => Ok(n),
+        }
+    }
+
+    /// Step the number in `line` up or down, keeping it within min/max
+    fn step(&self, line: &mut LineBuffer, up: bool) {
+        let current = parse_number::<T>(line.text().trim())
+            .or_else(|| self.default.as_deref().and_then(parse_number));
+
+        let mut n = match current {
+            Some(n) if up => n.step_up(self.step),
+            Some(n) => n.step_down(self.step),
+            None => self.min.unwrap_or_else(T::zero),
+        };
+        if let Some(min) = self.min {
+            if n < min {
+                n = min;
+            }
+        }
+        if let Some(max) = self.max {
+            if n > max {
+                n = max;
+            }
+        }
+        line.set(&n.to_string());
+    }
+
+    fn render<W: Terminal>(
+        &self,
+        stdout: &mut W,
+        theme: &Theme,
+        prompt: &mut LinePrompt,
+        line: &LineBuffer,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let mut head = Vec::new();
+        theme.print_question(&mut head, self.msg, self.default.as_deref())?;
+        write!(head, "{}", &line.text()[..line.cursor()])?;
+        let tail = &line.text()[line.cursor()..];
+
+        let mut below = Vec::new();
+        write!(below, "\r\n")?;
+        if let Some(error) = error {
+            theme.print_error(&mut below, error)?;
+        }
+
+        prompt.draw(stdout, &head, tail.as_bytes(), &below)?;
+        Ok(())
+    }
+}
+
+impl<'a, T: Numeric> Editor for Number<'a, T> {
+    type Output = T;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let mut prompt = LinePrompt::default();
+        let mut line = LineBuffer::default();
+        let mut error: Option<String> = None;
+
+        let n = loop {
+            self.render(&mut stdout, theme, &mut prompt, &line, error.as_deref())?;
+
+            let key = match keys.next() {
+                Some(key) => key?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            match key {
+                Key::Char('\n') => match self.parse(line.text()) {
+                    Ok(n) => break n,
+                    Err(err) => error = Some(err),
+                },
+                Key::Up => self.step(&mut line, true),
+                Key::Down => self.step(&mut line, false),
+                Key::Ctrl('c') => {
+                    write!(stdout, "\n\r")?;
+                    return Err(Error::UserAborted);
+                }
+                Key::Ctrl('d') if line.is_empty() => return Err(Error::NoMoreInput),
+                key => {
+                    line.edit(key);
+                }
+            }
+        };
+
+        // Clear a previous error below the input
+        prompt.clear(&mut stdout)?;
+        theme.print_results(&mut stdout, self.msg, &n.to_string())?;
+        Ok(n)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<T> {
+        let empty = !matches!(answer, Some(a) if !a.trim().is_empty());
+        if empty && self.default.is_none() {
+            return Err(Error::NoAnswer(self.msg.to_string()));
+        }
+
+        let n = self
+            .parse(answer.unwrap_or(""))
+            .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?;
+        print_answer(stdout, theme, self.msg, &n.to_string())?;
+        Ok(n)
+    }
+}
+
+pub fn number<T: Numeric>(msg: &str) -> Result<T> {
+    NumberBuilder::new(msg).build().run()
+}
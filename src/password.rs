@@ -1,17 +1,24 @@
-use super::editor::Editor;
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
 use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
-use std::io::{stdin, stdout, Read, Write};
+use std::io::{Read, Write};
+use termion::event::Key;
 use termion::input::TermRead;
 
 pub struct PasswordBuilder<'de> {
     msg: &'de str,
+    name: Option<&'de str>,
     theme: Option<Theme>,
 }
 
 impl<'de> PasswordBuilder<'de> {
     pub fn new(msg: &'de str) -> PasswordBuilder<'de> {
-        PasswordBuilder { msg, theme: None }
+        PasswordBuilder {
+            msg,
+            name: None,
+            theme: None,
+        }
     }
 
     pub fn theme(mut self, theme: Theme) -> PasswordBuilder<'de> {
@@ -19,16 +26,30 @@ impl<'de> PasswordBuilder<'de> {
         self
     }
 
+    pub fn name(mut self, name: &'de str) -> PasswordBuilder<'de> {
+        self.name = Some(name);
+        self
+    }
+
     pub fn build(self) -> Password<'de> {
         Password {
             msg: self.msg,
+            name: self.name,
             theme: self.theme,
         }
     }
 }
 
+impl<'de> IntoEditor for PasswordBuilder<'de> {
+    type Editor = Password<'de>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
 pub struct Password<'de> {
     msg: &'de str,
+    name: Option<&'de str>,
     theme: Option<Theme>,
 }
 
@@ -40,8 +61,8 @@ impl<'de> Password<'de> {
     pub fn run(&self) -> Result<String> {
         <Password as Editor>::run(
             self,
-            &mut stdin(),
-            &mut stdout(),
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
             self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
         )
     }
@@ -49,26 +70,60 @@ impl<'de> Password<'de> {
 
 impl<'de> Editor for Password<'de> {
     type Output = String;
-    fn run<R: Read, W: Write>(
+    fn run<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
     ) -> Result<Self::Output> {
-        theme.print_question(stdout, self.msg, None)?;
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+
+        theme.print_question(&mut stdout, self.msg, None)?;
 
         stdout.flush()?;
 
-        let pass = stdin.read_passwd(stdout)?;
+        let mut pass = String::new();
+
+        for key in stdin.keys() {
+            match key? {
+                Key::Char('\n') => {
+                    theme.print_results(&mut stdout, self.msg, "")?;
+                    return Ok(pass);
+                }
+                Key::Char(c) => pass.push(c),
+                Key::Backspace => {
+                    pass.pop();
+                }
+                Key::Ctrl('c') => {
+                    write!(stdout, "\n\r")?;
+                    return Err(Error::UserAborted);
+                }
+                Key::Ctrl('d') => break,
+                _ => {}
+            }
+        }
 
-        let pass = match pass {
-            Some(o) => o,
-            None => return Err(Error::NoMoreInput),
-        };
+        Err(Error::NoMoreInput)
+    }
 
-        theme.print_results(stdout, self.msg, "")?;
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
 
-        Ok(pass)
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<String> {
+        let pass = answer.ok_or_else(|| Error::NoAnswer(self.msg.to_string()))?;
+        print_answer(stdout, theme, self.msg, "")?;
+        Ok(pass.to_string())
     }
 }
 
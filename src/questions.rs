@@ -0,0 +1,409 @@
+use super::choice::Choice;
+use super::date::{Date, DateTime, Time};
+use super::editor::{Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::Terminal;
+use super::theme::Theme;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// The answer to a single question
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<Answer>),
+}
+
+impl Answer {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Answer::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Answer::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// The answer as a float, integers are converted
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Answer::Float(n) => Some(*n),
+            Answer::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Answer::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Answer]> {
+        match self {
+            Answer::List(l) => Some(l),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Answer {
+    fn from(b: bool) -> Self {
+        Answer::Bool(b)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
+
+macro_rules! int_answer {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            /// Numbers too large for an `Int` are kept as text
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Int(n),
+                    Err(_) => Answer::String(n.to_string()),
+                }
+            }
+        }
+
+        impl FromAnswer for $t {
+            fn from_answer(answer: &Answer) -> Option<Self> {
+                match answer {
+                    Answer::Int(n) => <$t>::try_from(*n).ok(),
+                    Answer::String(s) => s.parse().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! float_answer {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Float(n as f64)
+            }
+        }
+
+        impl FromAnswer for $t {
+            fn from_answer(answer: &Answer) -> Option<Self> {
+                answer.as_float().map(|n| n as $t)
+            }
+        }
+    )*};
+}
+
+int_answer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_answer!(f32, f64);
+
+/// Answers kept as their text, like dates and paths
+macro_rules! text_answer {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::String(value.to_string())
+            }
+        }
+
+        impl FromAnswer for $t {
+            fn from_answer(answer: &Answer) -> Option<Self> {
+                answer.as_str().and_then(|s| s.parse().ok())
+            }
+        }
+    )*};
+}
+
+text_answer!(Date, Time, DateTime);
+
+impl From<PathBuf> for Answer {
+    fn from(path: PathBuf) -> Self {
+        Answer::String(path.to_string_lossy().into_owned())
+    }
+}
+
+/// A picked choice is answered with its value
+impl<'a, C> From<&'a C> for Answer
+where
+    C: Choice,
+    C::Value: Clone + Into<Answer>,
+{
+    fn from(choice: &'a C) -> Self {
+        choice.value().clone().into()
+    }
+}
+
+/// The value of a `(text, value)` choice
+impl<T, V: Into<Answer>> From<(T, V)> for Answer {
+    fn from((_, value): (T, V)) -> Self {
+        value.into()
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(values: Vec<T>) -> Self {
+        Answer::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Answers to a list of questions, in the order they were asked
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    answers: Vec<(String, Answer)>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Answer> {
+        self.answers.iter().find(|(n, _)| n == name).map(|(_, a)| a)
+    }
+
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        self.get(name).and_then(Answer::as_bool)
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(Answer::as_str)
+    }
+
+    pub fn get_list(&self, name: &str) -> Option<&[Answer]> {
+        self.get(name).and_then(Answer::as_list)
+    }
+
+    pub fn insert(&mut self, name: impl ToString, answer: impl Into<Answer>) {
+        let name = name.to_string();
+        let answer = answer.into();
+        match self.answers.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = answer,
+            None => self.answers.push((name, answer)),
+        }
+    }
+
+    /// The answer to `name` converted to `T`
+    pub fn value<T: FromAnswer>(&self, name: &str) -> Result<T> {
+        self.optional(name)?
+            .ok_or_else(|| Error::NoAnswer(name.to_string()))
+    }
+
+    /// Like `value`, but a question that was skipped gives `None`
+    pub fn optional<T: FromAnswer>(&self, name: &str) -> Result<Option<T>> {
+        match self.get(name) {
+            Some(answer) => T::from_answer(answer).map(Some).ok_or_else(|| {
+                Error::InvalidAnswer(name.to_string(), format!("unexpected answer {:?}", answer))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Answer)> {
+        self.answers.iter().map(|(n, a)| (n.as_str(), a))
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+/// Types a single answer can be converted to
+pub trait FromAnswer: Sized {
+    fn from_answer(answer: &Answer) -> Option<Self>;
+}
+
+impl FromAnswer for Answer {
+    fn from_answer(answer: &Answer) -> Option<Self> {
+        Some(answer.clone())
+    }
+}
+
+impl FromAnswer for bool {
+    fn from_answer(answer: &Answer) -> Option<Self> {
+        answer.as_bool()
+    }
+}
+
+impl FromAnswer for String {
+    fn from_answer(answer: &Answer) -> Option<Self> {
+        answer.as_str().map(String::from)
+    }
+}
+
+impl FromAnswer for PathBuf {
+    fn from_answer(answer: &Answer) -> Option<Self> {
+        answer.as_str().map(PathBuf::from)
+    }
+}
+
+impl<T: FromAnswer> FromAnswer for Vec<T> {
+    fn from_answer(answer: &Answer) -> Option<Self> {
+        answer.as_list()?.iter().map(T::from_answer).collect()
+    }
+}
+
+/// Types that can be built from the answers to a questionnaire
+pub trait FromAnswers: Sized {
+    fn from_answers(answers: &Answers) -> Result<Self>;
+}
+
+impl FromAnswers for Answers {
+    fn from_answers(answers: &Answers) -> Result<Self> {
+        Ok(answers.clone())
+    }
+}
+
+/// An editor with its output erased to an `Answer`
+pub(crate) trait AnswerEditor {
+    fn run(&self, stdin: &mut dyn Read, stdout: &mut dyn Terminal, theme: &Theme)
+        -> Result<Answer>;
+
+    fn answer(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut dyn Terminal,
+        theme: &Theme,
+    ) -> Result<Answer>;
+}
+
+impl<E> AnswerEditor for E
+where
+    E: Editor,
+    E::Output: Into<Answer>,
+{
+    fn run(
+        &self,
+        mut stdin: &mut dyn Read,
+        mut stdout: &mut dyn Terminal,
+        theme: &Theme,
+    ) -> Result<Answer> {
+        Editor::run(self, &mut stdin, &mut stdout, theme).map(Into::into)
+    }
+
+    fn answer(
+        &self,
+        answer: Option<&str>,
+        mut stdout: &mut dyn Terminal,
+        theme: &Theme,
+    ) -> Result<Answer> {
+        Editor::answer(self, answer, &mut stdout, theme).map(Into::into)
+    }
+}
+
+type When<'a> = Box<dyn Fn(&Answers) -> bool + 'a>;
+
+pub(crate) struct Question<'a> {
+    pub(crate) name: String,
+    pub(crate) editor: Box<dyn AnswerEditor + 'a>,
+    pub(crate) when: Option<When<'a>>,
+}
+
+/// A question is named by its key in the answers, which is also the key of
+/// its pre-seeded answer
+impl<'q, 'a> Editor for &'q Question<'a> {
+    type Output = Answer;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Answer> {
+        self.editor.run(stdin, stdout, theme)
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Answer> {
+        self.editor.answer(answer, stdout, theme)
+    }
+}
+
+/// A list of named questions, asked in order by `Form::ask`
+///
+/// ```ignore
+/// let questions = Questions::new()
+///     .question("hungry", Confirm::new("Are you hungry?"))
+///     .question("food", Select::new("What do you want?", &choices))
+///     .when(|answers| answers.get_bool("hungry") == Some(true));
+///
+/// let answers = Form::default().ask(&questions)?;
+/// ```
+#[derive(Default)]
+pub struct Questions<'a> {
+    pub(crate) questions: Vec<Question<'a>>,
+}
+
+impl<'a> Questions<'a> {
+    pub fn new() -> Questions<'a> {
+        Questions::default()
+    }
+
+    /// Add a question, its answer is stored under `name`
+    pub fn question<E>(mut self, name: impl ToString, editor: E) -> Questions<'a>
+    where
+        E: IntoEditor,
+        E::Editor: 'a,
+        <E::Editor as Editor>::Output: Into<Answer>,
+    {
+        self.questions.push(Question {
+            name: name.to_string(),
+            editor: Box::new(editor.into_editor()),
+            when: None,
+        });
+        self
+    }
+
+    /// Only ask the last added question when `when` returns true for the
+    /// answers given so far
+    pub fn when<F: Fn(&Answers) -> bool + 'a>(mut self, when: F) -> Questions<'a> {
+        if let Some(question) = self.questions.last_mut() {
+            question.when = Some(Box::new(when));
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+}
@@ -1,91 +1,165 @@
-use super::choice::Choice;
-use super::editor::Editor;
+use super::choice::{find_selectable, is_selectable, Choice, Cursor, Selection};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
 use super::error::{Error, Result};
+use super::fuzzy::fuzzy_match;
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
 use super::theme::{Theme, DEFAULT_THEME};
-use std::io::{stdin, stdout, Read, Write};
+use std::borrow::Cow;
+use std::io::{Read, Write};
 use termion::event::Key;
 use termion::input::TermRead;
-use termion::raw::IntoRawMode;
 use termion::{clear, cursor};
 
-pub struct SelectBuilder<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
+pub struct SelectBuilder<'de, C: Clone> {
     msg: &'de str,
-    choices: &'de [C],
+    name: Option<&'de str>,
+    choices: Cow<'de, [C]>,
     page_size: usize,
+    filter: bool,
+    default: Option<usize>,
     theme: Option<Theme>,
 }
 
-impl<'de, C, V> SelectBuilder<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    pub fn new(msg: &'de str, choices: &'de [C]) -> SelectBuilder<'de, C, V> {
+impl<'de, C: Choice + Clone> SelectBuilder<'de, C> {
+    pub fn new(msg: &'de str, choices: impl Into<Cow<'de, [C]>>) -> SelectBuilder<'de, C> {
         SelectBuilder {
             msg,
-            choices,
+            name: None,
+            choices: choices.into(),
             page_size: 8,
+            filter: false,
+            default: None,
             theme: None,
         }
     }
 
-    pub fn page_size(mut self, size: usize) -> SelectBuilder<'de, C, V> {
-        self.page_size = size;
+    pub fn page_size(mut self, size: usize) -> SelectBuilder<'de, C> {
+        self.page_size = std::cmp::max(size, 1);
+        self
+    }
+
+    /// Narrow the choices with a fuzzy filter as the user types
+    pub fn filter(mut self, filter: bool) -> SelectBuilder<'de, C> {
+        self.filter = filter;
         self
     }
 
-    pub fn build(self) -> Select<'de, C, V> {
+    /// Index of the choice the cursor starts on, and the answer when none
+    /// is given. Ignored when out of range or not selectable.
+    pub fn default(mut self, index: usize) -> SelectBuilder<'de, C> {
+        self.default = self
+            .choices
+            .get(index)
+            .filter(|c| is_selectable(*c))
+            .map(|_| index);
+        self
+    }
+
+    /// Start on the first choice matching `predicate`, like `default`
+    pub fn default_by<F: Fn(&C) -> bool>(mut self, predicate: F) -> SelectBuilder<'de, C> {
+        self.default = self
+            .choices
+            .iter()
+            .position(|c| is_selectable(c) && predicate(c));
+        self
+    }
+
+    pub fn name(mut self, name: &'de str) -> SelectBuilder<'de, C> {
+        self.name = Some(name);
+        self
+    }
+
+    /// Build a `Select` returning where the choice picked was in the list,
+    /// along with its value
+    pub fn indexed(self) -> IndexedSelect<'de, C> {
+        IndexedSelect {
+            select: self.build(),
+        }
+    }
+
+    pub fn build(self) -> Select<'de, C> {
         Select {
             msg: self.msg,
+            name: self.name,
             choices: self.choices,
             page_size: self.page_size,
+            filter: self.filter,
+            default: self.default,
             theme: self.theme,
         }
     }
 }
 
-pub struct Select<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
+impl<'de, C: Choice + Clone> IntoEditor for SelectBuilder<'de, C> {
+    type Editor = Select<'de, C>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Picks one of a list of choices, which can be borrowed or owned. The
+/// choice picked is returned as a clone.
+pub struct Select<'de, C: Clone> {
     msg: &'de str,
-    choices: &'de [C],
+    name: Option<&'de str>,
+    choices: Cow<'de, [C]>,
     page_size: usize,
+    filter: bool,
+    default: Option<usize>,
     theme: Option<Theme>,
 }
 
-impl<'de, C, V> Select<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    pub fn new(msg: &'de str, choices: &'de [C]) -> SelectBuilder<'de, C, V> {
+impl<'de, C: Choice + Clone> Select<'de, C> {
+    pub fn new(msg: &'de str, choices: impl Into<Cow<'de, [C]>>) -> SelectBuilder<'de, C> {
         SelectBuilder::new(msg, choices)
     }
 
-    pub fn run(&self) -> Result<&'de C> {
-        <Select<'de, C, V> as Editor>::run(
+    pub fn run(&self) -> Result<C> {
+        <Select<'de, C> as Editor>::run(
             self,
-            &mut stdin(),
-            &mut stdout(),
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
             self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
         )
     }
-}
 
-impl<'de, C, V> Editor for Select<'de, C, V>
-where
-    C: Choice<Value = V>,
-{
-    type Output = &'de C;
-    fn run<R: Read, W: Write>(
+    /// Indices into `choices` matching `query`, best match first,
+    /// together with the matched char positions
+    fn filtered(&self, query: &str) -> Vec<(usize, Vec<usize>)> {
+        if query.is_empty() {
+            return (0..self.choices.len()).map(|i| (i, Vec::new())).collect();
+        }
+
+        let mut matches = self
+            .choices
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_separator())
+            .filter_map(|(i, c)| fuzzy_match(query, &c.text().to_string()).map(|m| (i, m)))
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.1.score));
+
+        matches.into_iter().map(|(i, m)| (i, m.indices)).collect()
+    }
+
+    /// Index of the choice picked
+    fn pick<R: Read, W: Terminal>(
         &self,
         stdin: &mut R,
         stdout: &mut W,
         theme: &Theme,
-    ) -> Result<Self::Output> {
-        let mut stdout = stdout.into_raw_mode()?;
+    ) -> Result<usize> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer_index(answer.as_deref(), stdout, theme);
+        }
+
+        // An empty list has nothing to pick
+        if self.choices.is_empty() {
+            return Err(Error::NoAnswer(self.msg.to_string()));
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
 
         theme.print_question(&mut stdout, self.msg, None)?;
         write!(&mut stdout, "\n{}", cursor::Hide)?;
@@ -96,38 +170,68 @@ where
             write!(&mut stdout, "\n")?;
         }
 
-        let mut cur: usize = 0;
-        let mut offset: usize = 0;
+        let mut query = String::new();
+        let mut filtered = self.filtered(&query);
+
+        let selectable = |filtered: &[(usize, Vec<usize>)], i: usize| {
+            is_selectable(&self.choices[filtered[i].0])
+        };
+        // Scroll just far enough for the default to be on the page
+        let mut cursor = Cursor::new(filtered.len(), rows, self.default.unwrap_or(0), |i| {
+            selectable(&filtered, i)
+        });
 
         let mut input = stdin.keys();
 
         loop {
+            let visible = std::cmp::min(filtered.len(), rows);
+
             write!(stdout, "{}", cursor::Up(rows as u16))?;
 
-            for (i, s) in self.choices.iter().skip(offset).take(rows).enumerate() {
+            if self.filter {
+                theme.print_question(&mut stdout, self.msg, None)?;
+                write!(stdout, "{}", query)?;
+            }
+
+            let cur = cursor.row();
+            for (i, (idx, matches)) in filtered.iter().skip(cursor.offset).take(rows).enumerate() {
+                let choice = &self.choices[*idx];
+                write!(&mut stdout, "\n\r{}", clear::CurrentLine)?;
+                if !is_selectable(choice) {
+                    theme.print_unselectable(&mut stdout, choice, false)?;
+                } else if self.filter {
+                    theme.print_filtered_choice(&mut stdout, choice, cur == i, matches)?;
+                } else {
+                    theme.print_choice(&mut stdout, choice, cur == i)?;
+                }
+            }
+
+            for _ in visible..rows {
                 write!(&mut stdout, "\n\r{}", clear::CurrentLine)?;
-                theme.print_choice(&mut stdout, s, cur == i)?;
             }
 
             stdout.flush()?;
 
-            let next = input.next().unwrap();
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
 
-            match next? {
-                Key::Char('\n') => {
+            match next {
+                Key::Char('\n') if visible > 0 && selectable(&filtered, cursor.pos) => {
                     break;
                 }
-                Key::Up if cur != 0 => {
-                    cur -= 1;
-                }
-                Key::Up if cur == 0 && offset > 0 => {
-                    offset -= 1;
+                Key::Up => cursor.up(|i| selectable(&filtered, i)),
+                Key::Down => cursor.down(|i| selectable(&filtered, i)),
+                Key::Char(c) if self.filter && c != '\n' => {
+                    query.push(c);
+                    filtered = self.filtered(&query);
+                    cursor = Cursor::new(filtered.len(), rows, 0, |i| selectable(&filtered, i));
                 }
-                Key::Down if cur != rows - 1 => {
-                    cur += 1;
-                }
-                Key::Down if (cur == rows - 1) && offset < (self.choices.len() - rows) => {
-                    offset += 1;
+                Key::Backspace if self.filter && !query.is_empty() => {
+                    query.pop();
+                    filtered = self.filtered(&query);
+                    cursor = Cursor::new(filtered.len(), rows, 0, |i| selectable(&filtered, i));
                 }
                 Key::Ctrl('c') => {
                     write!(stdout, "\n\r{}", cursor::Show)?;
@@ -137,25 +241,139 @@ where
             }
         }
 
-        for _ in 0..(rows + 1) {
+        for _ in 0..rows {
             write!(stdout, "{}{}", clear::CurrentLine, cursor::Up(1))?;
         }
 
-        write!(stdout, "\n\r{}", cursor::Show)?;
+        write!(stdout, "\r{}", cursor::Show)?;
+
+        let idx = filtered[cursor.pos].0;
 
         theme.print_results(
             &mut stdout,
             self.msg,
-            self.choices[offset + cur].text().to_string().as_str(),
+            self.choices[idx].text().to_string().as_str(),
         )?;
 
-        Ok(&self.choices[offset + cur])
+        Ok(idx)
     }
+
+    fn answer_index<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<usize> {
+        let idx = match (answer, self.default) {
+            (Some(answer), _) if !answer.trim().is_empty() => {
+                find_selectable(self.msg, &self.choices, answer)?
+            }
+            (_, Some(idx)) => idx,
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        print_answer(
+            stdout,
+            theme,
+            self.msg,
+            &self.choices[idx].text().to_string(),
+        )?;
+        Ok(idx)
+    }
+}
+
+impl<'de, C: Choice + Clone> Editor for Select<'de, C> {
+    type Output = C;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.pick(stdin, stdout, theme)
+            .map(|idx| self.choices[idx].clone())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<C> {
+        self.answer_index(answer, stdout, theme)
+            .map(|idx| self.choices[idx].clone())
+    }
+}
+
+pub fn select<'de, C: Choice + Clone + 'de>(
+    msg: &'de str,
+    choices: impl Into<Cow<'de, [C]>>,
+) -> Result<C> {
+    SelectBuilder::new(msg, choices).build().run()
+}
+
+/// A `Select` returning the index and the value of the choice picked
+pub struct IndexedSelect<'de, C: Clone> {
+    select: Select<'de, C>,
 }
 
-pub fn select<'de, C, V>(msg: &'de str, choices: &'de [C]) -> Result<&'de C>
+impl<'de, C> IndexedSelect<'de, C>
 where
-    C: Choice<Value = V>,
+    C: Choice + Clone,
+    C::Value: Clone,
 {
-    SelectBuilder::new(msg, choices).build().run()
+    pub fn run(&self) -> Result<Selection<C::Value>> {
+        <IndexedSelect<'de, C> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.select.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn selection(&self, index: usize) -> Selection<C::Value> {
+        Selection {
+            index,
+            value: self.select.choices[index].value().clone(),
+        }
+    }
+}
+
+impl<'de, C> Editor for IndexedSelect<'de, C>
+where
+    C: Choice + Clone,
+    C::Value: Clone,
+{
+    type Output = Selection<C::Value>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.select
+            .pick(stdin, stdout, theme)
+            .map(|idx| self.selection(idx))
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.select.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.select
+            .answer_index(answer, stdout, theme)
+            .map(|idx| self.selection(idx))
+    }
 }
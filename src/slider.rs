@@ -0,0 +1,283 @@
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::number::{parse_number, Numeric};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use termion::{clear, cursor, event::Key, input::TermRead};
+use unicode_width::UnicodeWidthStr;
+
+/// Number of steps PgUp/PgDn move when no big step is set
+const BIG_STEPS: usize = 10;
+
+pub struct SliderBuilder<'a, T: Numeric> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    min: T,
+    max: T,
+    default: Option<T>,
+    step: T,
+    big_step: Option<T>,
+    width: usize,
+    labels: Option<(&'a str, &'a str)>,
+}
+
+impl<'a, T: Numeric> SliderBuilder<'a, T> {
+    pub fn new(msg: &'a str, min: T, max: T) -> SliderBuilder<'a, T> {
+        SliderBuilder {
+            msg,
+            name: None,
+            theme: None,
+            min,
+            max,
+            default: None,
+            step: T::one(),
+            big_step: None,
+            width: 30,
+            labels: None,
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> SliderBuilder<'a, T> {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Value the slider starts at, `min` by default
+    pub fn default(mut self, default: T) -> SliderBuilder<'a, T> {
+        self.default = Some(default);
+        self
+    }
+
+    /// Amount Left/Right and `h`/`l` change the value by
+    pub fn step(mut self, step: T) -> SliderBuilder<'a, T> {
+        self.step = step;
+        self
+    }
+
+    /// Amount PgUp/PgDn and `H`/`L` change the value by, ten steps by default
+    pub fn big_step(mut self, step: T) -> SliderBuilder<'a, T> {
+        self.big_step = Some(step);
+        self
+    }
+
+    /// Width of the bar in cells, it is narrowed to fit the terminal
+    pub fn width(mut self, width: usize) -> SliderBuilder<'a, T> {
+        self.width = std::cmp::max(width, 2);
+        self
+    }
+
+    /// Text drawn at the ends of the bar
+    pub fn labels(mut self, left: &'a str, right: &'a str) -> SliderBuilder<'a, T> {
+        self.labels = Some((left, right));
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> SliderBuilder<'a, T> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Slider<'a, T> {
+        Slider {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            min: self.min,
+            max: self.max,
+            default: self.default,
+            step: self.step,
+            big_step: self.big_step,
+            width: self.width,
+            labels: self.labels,
+        }
+    }
+}
+
+impl<'a, T: Numeric> IntoEditor for SliderBuilder<'a, T> {
+    type Editor = Slider<'a, T>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// A number between bounds, picked on a horizontal bar.
+///
+/// Left/Right, Up/Down and `h`/`l` move by a step, PgUp/PgDn and `H`/`L` by
+/// a big step, Home/End to the bounds. termion 1.5 can't decode
+/// Shift+arrow, so `H`/`L` stand in for it.
+pub struct Slider<'a, T: Numeric> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    min: T,
+    max: T,
+    default: Option<T>,
+    step: T,
+    big_step: Option<T>,
+    width: usize,
+    labels: Option<(&'a str, &'a str)>,
+}
+
+impl<'a, T: Numeric> Slider<'a, T> {
+    pub fn new(msg: &'a str, min: T, max: T) -> SliderBuilder<'a, T> {
+        SliderBuilder::new(msg, min, max)
+    }
+
+    pub fn run(&self) -> Result<T> {
+        <Slider<T> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn clamp(&self, n: T) -> T {
+        if n < self.min {
+            self.min
+        } else if n > self.max {
+            self.max
+        } else {
+            n
+        }
+    }
+
+    fn initial(&self) -> T {
+        self.clamp(self.default.unwrap_or(self.min))
+    }
+
+    /// Move `n` by a step, or a big step, up or down
+    fn slide(&self, n: T, up: bool, big: bool) -> T {
+        let (step, times) = match (big, self.big_step) {
+            (true, Some(big_step)) => (big_step, 1),
+            (true, None) => (self.step, BIG_STEPS),
+            (false, _) => (self.step, 1),
+        };
+
+        let mut n = n;
+        for _ in 0..times {
+            n = if up {
+                n.step_up(step)
+            } else {
+                n.step_down(step)
+            };
+        }
+        self.clamp(n)
+    }
+
+    fn parse(&self, input: &str) -> std::result::Result<T, String> {
+        let input = input.trim();
+        let n =
+            parse_number::<T>(input).ok_or_else(|| format!("{:?} is not a valid number", input))?;
+        if n < self.min || n > self.max {
+            return Err(format!("Must be between {} and {}", self.min, self.max));
+        }
+        Ok(n)
+    }
+
+    /// Cell of the bar the handle is drawn in
+    fn handle(&self, n: T, width: usize) -> usize {
+        let range = self.max.to_f64() - self.min.to_f64();
+        if range <= 0.0 {
+            return 0;
+        }
+        let frac = (n.to_f64() - self.min.to_f64()) / range;
+        (frac * (width - 1) as f64).round() as usize
+    }
+
+    fn render<W: Terminal>(&self, stdout: &mut W, theme: &Theme, n: T, width: usize) -> Result<()> {
+        write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        theme.print_slider(stdout, self.handle(n, width), width, self.labels)?;
+        write!(stdout, "{}\r", cursor::Up(1))?;
+        theme.print_question(stdout, self.msg, None)?;
+        write!(stdout, "{}", n)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+impl<'a, T: Numeric> Editor for Slider<'a, T> {
+    type Output = T;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        // Leave room for the indent, the labels and the spaces around them
+        let labels = self
+            .labels
+            .map(|(left, right)| left.width() + right.width() + 2)
+            .unwrap_or(0);
+        let room = (stdout.size()?.0 as usize).saturating_sub(labels + 3);
+        let width = std::cmp::max(std::cmp::min(self.width, room), 2);
+
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let mut n = self.initial();
+
+        write!(stdout, "{}", cursor::Hide)?;
+
+        loop {
+            self.render(&mut stdout, theme, n, width)?;
+
+            let key = match keys.next() {
+                Some(key) => key?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            n = match key {
+                Key::Char('\n') => break,
+                Key::Ctrl('c') => {
+                    write!(stdout, "\r\n{}{}", clear::CurrentLine, cursor::Show)?;
+                    return Err(Error::UserAborted);
+                }
+                Key::Right | Key::Up | Key::Char('l') => self.slide(n, true, false),
+                Key::Left | Key::Down | Key::Char('h') => self.slide(n, false, false),
+                Key::PageUp | Key::Char('L') => self.slide(n, true, true),
+                Key::PageDown | Key::Char('H') => self.slide(n, false, true),
+                Key::Home => self.min,
+                Key::End => self.max,
+                _ => n,
+            };
+        }
+
+        write!(stdout, "\r\n{}{}", clear::CurrentLine, cursor::Up(1))?;
+        write!(stdout, "{}", cursor::Show)?;
+        theme.print_results(&mut stdout, self.msg, &n.to_string())?;
+        Ok(n)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<T> {
+        let n = match answer {
+            Some(answer) if !answer.trim().is_empty() => self
+                .parse(answer)
+                .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?,
+            _ => self.initial(),
+        };
+
+        print_answer(stdout, theme, self.msg, &n.to_string())?;
+        Ok(n)
+    }
+}
+
+pub fn slider<T: Numeric>(msg: &str, min: T, max: T) -> Result<T> {
+    SliderBuilder::new(msg, min, max).build().run()
+}
@@ -0,0 +1,292 @@
+use super::choice::{find_choice, Choice, Cursor};
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::{clear, cursor};
+
+pub struct SortBuilder<'de, C> {
+    msg: &'de str,
+    name: Option<&'de str>,
+    choices: &'de [C],
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'de, C: Choice> SortBuilder<'de, C> {
+    pub fn new(msg: &'de str, choices: &'de [C]) -> SortBuilder<'de, C> {
+        SortBuilder {
+            msg,
+            name: None,
+            choices,
+            page_size: 8,
+            theme: None,
+        }
+    }
+
+    pub fn page_size(mut self, size: usize) -> SortBuilder<'de, C> {
+        self.page_size = std::cmp::max(size, 1);
+        self
+    }
+
+    pub fn name(mut self, name: &'de str) -> SortBuilder<'de, C> {
+        self.name = Some(name);
+        self
+    }
+
+    /// Build a `Sort` returning where the choices were in the list, in
+    /// their new order
+    pub fn indexed(self) -> IndexedSort<'de, C> {
+        IndexedSort { sort: self.build() }
+    }
+
+    pub fn build(self) -> Sort<'de, C> {
+        Sort {
+            msg: self.msg,
+            name: self.name,
+            choices: self.choices,
+            page_size: self.page_size,
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'de, C: Choice> IntoEditor for SortBuilder<'de, C> {
+    type Editor = Sort<'de, C>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Puts choices in order. Space grabs the highlighted choice, which then
+/// moves with Up/Down until Space drops it.
+pub struct Sort<'de, C> {
+    msg: &'de str,
+    name: Option<&'de str>,
+    choices: &'de [C],
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'de, C: Choice> Sort<'de, C> {
+    pub fn new(msg: &'de str, choices: &'de [C]) -> SortBuilder<'de, C> {
+        SortBuilder::new(msg, choices)
+    }
+
+    pub fn run(&self) -> Result<Vec<&'de C>> {
+        <Sort<'de, C> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn order<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Vec<usize>> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer_order(answer.as_deref(), stdout, theme);
+        }
+
+        let mut order = (0..self.choices.len()).collect::<Vec<_>>();
+        let mut stdout = RawMode::new(stdout)?;
+
+        theme.print_question(&mut stdout, self.msg, None)?;
+        if order.is_empty() {
+            theme.print_results(&mut stdout, self.msg, "")?;
+            return Ok(order);
+        }
+        write!(&mut stdout, "\n{}", cursor::Hide)?;
+
+        let rows = std::cmp::min(order.len(), self.page_size);
+
+        for _ in 0..rows - 1 {
+            writeln!(&mut stdout)?;
+        }
+
+        let mut cursor = Cursor::new(order.len(), rows, 0, |_| true);
+        let mut grabbed = false;
+
+        let mut input = stdin.keys();
+
+        loop {
+            write!(stdout, "{}", cursor::Up(rows as u16))?;
+
+            for (i, idx) in order.iter().enumerate().skip(cursor.offset).take(rows) {
+                write!(&mut stdout, "\n\r{}", clear::CurrentLine)?;
+                theme.print_ranked_choice(
+                    &mut stdout,
+                    i + 1,
+                    &self.choices[*idx],
+                    cursor.pos == i,
+                    grabbed && cursor.pos == i,
+                )?;
+            }
+
+            stdout.flush()?;
+
+            let next = match input.next() {
+                Some(next) => next?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            let pos = cursor.pos;
+            match next {
+                Key::Char('\n') => {
+                    break;
+                }
+                Key::Char(' ') => {
+                    grabbed = !grabbed;
+                }
+                Key::Up => {
+                    cursor.up(|_| true);
+                    if grabbed {
+                        order.swap(pos, cursor.pos);
+                    }
+                }
+                Key::Down => {
+                    cursor.down(|_| true);
+                    if grabbed {
+                        order.swap(pos, cursor.pos);
+                    }
+                }
+                Key::Ctrl('c') => {
+                    write!(stdout, "\n\r{}", cursor::Show)?;
+                    return Err(Error::UserAborted);
+                }
+                _ => {}
+            }
+        }
+
+        for _ in 0..rows {
+            write!(stdout, "{}{}", clear::CurrentLine, cursor::Up(1))?;
+        }
+
+        write!(stdout, "\r{}", cursor::Show)?;
+
+        theme.print_results(&mut stdout, self.msg, &self.summary(&order))?;
+
+        Ok(order)
+    }
+
+    /// The choices listed in the answer come first, the others follow in
+    /// their original order, so an empty line keeps the original order.
+    fn answer_order<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Vec<usize>> {
+        let answer = answer.ok_or_else(|| Error::NoAnswer(self.msg.to_string()))?;
+
+        let mut order = Vec::new();
+        for part in answer.split(',').filter(|p| !p.trim().is_empty()) {
+            let idx = find_choice(self.msg, self.choices, part)?;
+            if !order.contains(&idx) {
+                order.push(idx);
+            }
+        }
+        for idx in 0..self.choices.len() {
+            if !order.contains(&idx) {
+                order.push(idx);
+            }
+        }
+
+        print_answer(stdout, theme, self.msg, &self.summary(&order))?;
+        Ok(order)
+    }
+
+    fn summary(&self, order: &[usize]) -> String {
+        order
+            .iter()
+            .map(|idx| self.choices[*idx].text().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn choices(&self, order: Vec<usize>) -> Vec<&'de C> {
+        order.into_iter().map(|idx| &self.choices[idx]).collect()
+    }
+}
+
+impl<'de, C: Choice> Editor for Sort<'de, C> {
+    type Output = Vec<&'de C>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.order(stdin, stdout, theme)
+            .map(|order| self.choices(order))
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Vec<&'de C>> {
+        self.answer_order(answer, stdout, theme)
+            .map(|order| self.choices(order))
+    }
+}
+
+/// A `Sort` returning the indices of the choices in their new order
+pub struct IndexedSort<'de, C> {
+    sort: Sort<'de, C>,
+}
+
+impl<'de, C: Choice> IndexedSort<'de, C> {
+    pub fn run(&self) -> Result<Vec<usize>> {
+        <IndexedSort<'de, C> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.sort.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+}
+
+impl<'de, C: Choice> Editor for IndexedSort<'de, C> {
+    type Output = Vec<usize>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.sort.order(stdin, stdout, theme)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.sort.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        self.sort.answer_order(answer, stdout, theme)
+    }
+}
+
+pub fn sort<'de, C: Choice>(msg: &'de str, choices: &'de [C]) -> Result<Vec<&'de C>> {
+    SortBuilder::new(msg, choices).build().run()
+}
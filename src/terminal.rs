@@ -0,0 +1,334 @@
+use std::io::{self, stdin, stdout, Read, Stdout, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+use termion::raw::{IntoRawMode, RawTerminal};
+
+lazy_static::lazy_static! {
+    /// Raw mode of the process' stdout, shared by every handle to it
+    static ref RAW_STDOUT: Mutex<Option<RawTerminal<Stdout>>> = Mutex::new(None);
+
+    /// Reads of the process' stdin, shared by every handle to it
+    static ref STDIN: Mutex<Channel> = Mutex::new(Channel::default());
+}
+
+/// Asks the reader thread for a read, and receives what it read
+type Reader = (Sender<()>, Receiver<io::Result<Vec<u8>>>);
+
+/// Reads stdin on a thread of its own, so a read can be waited for with a
+/// timeout. The thread only reads when asked to, which leaves stdin alone
+/// while other programs, like an external editor, use it.
+#[derive(Default)]
+struct Channel {
+    /// Started on the first read
+    thread: Option<Reader>,
+    /// Bytes read but not yet taken
+    pending: Vec<u8>,
+    /// Whether the last read hit the end of stdin
+    eof: bool,
+    /// Whether the thread was asked for a read it has not sent yet
+    waiting: bool,
+    /// Whether stdin was read through `StdinWrap`. Until then polls leave
+    /// stdin alone, bytes they read would never reach another reader.
+    shared: bool,
+}
+
+impl Channel {
+    fn lock() -> MutexGuard<'static, Channel> {
+        STDIN.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait up to `timeout`, or for as long as it takes, for bytes or the
+    /// end of stdin, returning whether either came
+    fn poll(&mut self, timeout: Option<Duration>) -> io::Result<bool> {
+        if !self.pending.is_empty() || self.eof {
+            return Ok(true);
+        }
+
+        let (requests, replies) = self.thread.get_or_insert_with(|| {
+            let (requests, asked) = mpsc::channel::<()>();
+            let (reply, replies) = mpsc::channel();
+            thread::spawn(move || {
+                let mut buf = [0; 1024];
+                for _ in asked {
+                    let read = stdin().read(&mut buf).map(|n| buf[..n].to_vec());
+                    if reply.send(read).is_err() {
+                        break;
+                    }
+                }
+            });
+            (requests, replies)
+        });
+
+        let gone = || io::Error::new(io::ErrorKind::BrokenPipe, "stdin reader stopped");
+        if !self.waiting {
+            requests.send(()).map_err(|_| gone())?;
+            self.waiting = true;
+        }
+        let read = match timeout {
+            Some(timeout) => match replies.recv_timeout(timeout) {
+                Ok(read) => read,
+                Err(RecvTimeoutError::Timeout) => return Ok(false),
+                Err(RecvTimeoutError::Disconnected) => return Err(gone()),
+            },
+            None => replies.recv().map_err(|_| gone())?,
+        };
+        self.waiting = false;
+
+        let bytes = read?;
+        self.eof = bytes.is_empty();
+        self.pending = bytes;
+        Ok(true)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        self.poll(None)?;
+        if self.eof {
+            // Terminals can be read from again after an end of input
+            self.eof = false;
+            return Ok(0);
+        }
+        let n = std::cmp::min(buf.len(), self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
+
+/// The process' stdin, read so that `StdoutWrap` can wait for keys.
+///
+/// Use it rather than `std::io::stdin()` as the reader of a `Form` drawing
+/// on stdout. Waiting for keys reads them ahead, and only `StdinWrap` gets
+/// the keys read ahead, so stdout only waits once stdin was read through
+/// it. Don't read stdin both ways in one process.
+pub struct StdinWrap;
+
+impl StdinWrap {
+    pub fn new() -> StdinWrap {
+        StdinWrap
+    }
+}
+
+impl Default for StdinWrap {
+    fn default() -> Self {
+        StdinWrap::new()
+    }
+}
+
+impl Read for StdinWrap {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut channel = Channel::lock();
+        channel.shared = true;
+        channel.read(buf)
+    }
+}
+
+/// An output the editors draw on.
+///
+/// Implemented for the process' stdout and for `Vec<u8>`, other writers can
+/// be wrapped in `Plain`.
+pub trait Terminal: Write {
+    /// Whether a user is typing on the other end. When not, the editors
+    /// read plain lines instead of keys and draw nothing but the answers.
+    fn is_interactive(&self) -> bool;
+
+    /// Switch raw mode on or off, returning the previous state
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool>;
+
+    /// Columns and rows of the terminal
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok((80, 24))
+    }
+
+    /// Wait up to `timeout` for a key to be typed, returning whether one
+    /// can be read. Terminals that cannot tell return `false` right away.
+    ///
+    /// The process' stdout waits on the keys read through `StdinWrap`, and
+    /// returns `false` right away until stdin was read through it.
+    fn poll_input(&self, timeout: Duration) -> io::Result<bool> {
+        let _ = timeout;
+        Ok(false)
+    }
+}
+
+impl<T: Terminal + ?Sized> Terminal for &mut T {
+    fn is_interactive(&self) -> bool {
+        (**self).is_interactive()
+    }
+
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool> {
+        (**self).set_raw_mode(raw)
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        (**self).size()
+    }
+
+    fn poll_input(&self, timeout: Duration) -> io::Result<bool> {
+        (**self).poll_input(timeout)
+    }
+}
+
+impl Terminal for Vec<u8> {
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn set_raw_mode(&mut self, _raw: bool) -> io::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// A writer that is not a terminal, the editors read plain lines and write
+/// nothing but the answers to it
+pub struct Plain<W>(pub W);
+
+impl<W: Write> Write for Plain<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: Write> Terminal for Plain<W> {
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    fn set_raw_mode(&mut self, _raw: bool) -> io::Result<bool> {
+        Ok(false)
+    }
+}
+
+fn set_stdout_raw_mode(raw: bool) -> io::Result<bool> {
+    let mut state = RAW_STDOUT.lock().unwrap_or_else(|e| e.into_inner());
+    let prev = state.is_some();
+    if raw && !prev {
+        *state = Some(stdout().into_raw_mode()?);
+    } else if !raw {
+        // Dropping the raw terminal restores the original mode
+        *state = None;
+    }
+    Ok(prev)
+}
+
+impl Terminal for Stdout {
+    fn is_interactive(&self) -> bool {
+        termion::is_tty(&stdin()) && termion::is_tty(self)
+    }
+
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool> {
+        set_stdout_raw_mode(raw)
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        termion::terminal_size()
+    }
+
+    fn poll_input(&self, timeout: Duration) -> io::Result<bool> {
+        let mut channel = Channel::lock();
+        if !channel.shared {
+            return Ok(false);
+        }
+        channel.poll(Some(timeout))
+    }
+}
+
+/// The process' stdout
+pub struct StdoutWrap {
+    out: Stdout,
+}
+
+impl StdoutWrap {
+    pub fn new() -> StdoutWrap {
+        StdoutWrap { out: stdout() }
+    }
+}
+
+impl Default for StdoutWrap {
+    fn default() -> Self {
+        StdoutWrap::new()
+    }
+}
+
+impl Write for StdoutWrap {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.lock().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.lock().flush()
+    }
+}
+
+impl Terminal for StdoutWrap {
+    fn is_interactive(&self) -> bool {
+        self.out.is_interactive()
+    }
+
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool> {
+        set_stdout_raw_mode(raw)
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        termion::terminal_size()
+    }
+
+    fn poll_input(&self, timeout: Duration) -> io::Result<bool> {
+        self.out.poll_input(timeout)
+    }
+}
+
+/// Keeps a terminal in raw mode until dropped
+pub struct RawMode<'a, T: Terminal + ?Sized> {
+    term: &'a mut T,
+    prev: bool,
+}
+
+impl<'a, T: Terminal + ?Sized> RawMode<'a, T> {
+    pub fn new(term: &'a mut T) -> io::Result<RawMode<'a, T>> {
+        let prev = term.set_raw_mode(true)?;
+        Ok(RawMode { term, prev })
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Write for RawMode<'a, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.term.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.term.flush()
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Terminal for RawMode<'a, T> {
+    fn is_interactive(&self) -> bool {
+        self.term.is_interactive()
+    }
+
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool> {
+        self.term.set_raw_mode(raw)
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        self.term.size()
+    }
+
+    fn poll_input(&self, timeout: Duration) -> io::Result<bool> {
+        self.term.poll_input(timeout)
+    }
+}
+
+impl<'a, T: Terminal + ?Sized> Drop for RawMode<'a, T> {
+    fn drop(&mut self) {
+        let _ = self.term.set_raw_mode(self.prev);
+    }
+}
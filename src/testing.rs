@@ -0,0 +1,514 @@
+//! Headless backend for driving editors without a tty
+//!
+//! A [`Script`] feeds scripted keys to an editor and a [`VirtualTerminal`]
+//! interprets what the editor draws into a grid of cells.
+//!
+//! ```ignore
+//! let term = VirtualTerminal::new(40, 10);
+//! let keys = term.input(Script::new().text("Rasmus").enter());
+//! let mut form = Form::new(keys, term.clone(), Theme::new());
+//! assert_eq!(form.run(Input::new("Name")).unwrap(), "Rasmus");
+//! assert_eq!(term.screen()[0], "? Name Rasmus");
+//! ```
+use super::terminal::Terminal;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::time::Duration;
+use termion::event::Key;
+use unicode_width::UnicodeWidthChar;
+
+/// A scripted sequence of keys, readable as stdin
+///
+/// Every read yields at most one key, so an escape key is never merged
+/// with the key that follows it.
+#[derive(Debug, Default, Clone)]
+pub struct Script {
+    /// `None` marks a pause in the typing
+    keys: VecDeque<Option<Vec<u8>>>,
+}
+
+impl Script {
+    pub fn new() -> Script {
+        Script::default()
+    }
+
+    pub fn key(mut self, key: Key) -> Script {
+        self.keys.push_back(Some(encode(key)));
+        self
+    }
+
+    pub fn keys(self, keys: impl IntoIterator<Item = Key>) -> Script {
+        keys.into_iter().fold(self, Script::key)
+    }
+
+    /// Type every char of `text`
+    pub fn text(self, text: &str) -> Script {
+        self.keys(text.chars().map(Key::Char))
+    }
+
+    pub fn enter(self) -> Script {
+        self.key(Key::Char('\n'))
+    }
+
+    /// Stop typing for a while, long enough for a terminal polled for
+    /// input to time out
+    pub fn pause(mut self) -> Script {
+        self.keys.push_back(None);
+        self
+    }
+
+    /// Whether every key has been read
+    pub fn is_empty(&self) -> bool {
+        self.keys.iter().all(Option::is_none)
+    }
+
+    /// Whether a key is typed before the next pause, which is over once
+    /// polled
+    fn poll(&mut self) -> bool {
+        match self.keys.front() {
+            Some(Some(_)) => true,
+            Some(None) => {
+                self.keys.pop_front();
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+impl Read for Script {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Reading goes on typing after a pause
+        while let Some(None) = self.keys.front() {
+            self.keys.pop_front();
+        }
+        let key = match self.keys.front_mut() {
+            Some(Some(key)) => key,
+            _ => return Ok(0),
+        };
+
+        let n = std::cmp::min(buf.len(), key.len());
+        buf[..n].copy_from_slice(&key[..n]);
+        key.drain(..n);
+        if key.is_empty() {
+            self.keys.pop_front();
+        }
+        Ok(n)
+    }
+}
+
+fn encode(key: Key) -> Vec<u8> {
+    match key {
+        Key::Null => vec![0],
+        Key::Char(c) => c.to_string().into_bytes(),
+        Key::Alt(c) => format!("\x1b{}", c).into_bytes(),
+        Key::Ctrl(c @ 'a'..='z') => vec![c as u8 - b'a' + 1],
+        Key::Ctrl(c @ '4'..='7') => vec![c as u8 - b'4' + 0x1c],
+        Key::Ctrl(c) => vec![c as u8 & 0x1f],
+        Key::Backspace => vec![0x7f],
+        Key::Esc => vec![0x1b],
+        Key::Left => b"\x1b[D".to_vec(),
+        Key::Right => b"\x1b[C".to_vec(),
+        Key::Up => b"\x1b[A".to_vec(),
+        Key::Down => b"\x1b[B".to_vec(),
+        Key::Home => b"\x1b[H".to_vec(),
+        Key::End => b"\x1b[F".to_vec(),
+        Key::BackTab => b"\x1b[Z".to_vec(),
+        Key::Insert => b"\x1b[2~".to_vec(),
+        Key::Delete => b"\x1b[3~".to_vec(),
+        Key::PageUp => b"\x1b[5~".to_vec(),
+        Key::PageDown => b"\x1b[6~".to_vec(),
+        Key::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+        Key::F(n) => {
+            let code = match n {
+                5 => 15,
+                6..=10 => n + 11,
+                _ => n + 12,
+            };
+            format!("\x1b[{}~", code).into_bytes()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// An in-memory terminal recording what is drawn on it
+///
+/// Understands the subset of ANSI escapes the editors emit: cursor
+/// movement, line and screen clearing, cursor visibility and colors (which
+/// are dropped). Lines scrolled off the top are kept in a scrollback.
+///
+/// Clones share the same screen, so a test can hand one to a `Form` and
+/// inspect the other.
+#[derive(Debug, Clone)]
+pub struct VirtualTerminal {
+    screen: Rc<RefCell<Screen>>,
+    script: Rc<RefCell<Script>>,
+}
+
+impl VirtualTerminal {
+    pub fn new(width: u16, height: u16) -> VirtualTerminal {
+        VirtualTerminal {
+            screen: Rc::new(RefCell::new(Screen::new(width, height))),
+            script: Rc::new(RefCell::new(Script::new())),
+        }
+    }
+
+    /// Keyboard input typing `script` into this terminal
+    ///
+    /// Like a tty, keys are echoed on the screen while not in raw mode.
+    /// Nothing is echoed when not interactive.
+    ///
+    /// Polling the terminal for input sees the keys of the script.
+    pub fn input(&self, script: Script) -> VirtualInput {
+        *self.script.borrow_mut() = script;
+        VirtualInput {
+            script: self.script.clone(),
+            screen: self.screen.clone(),
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        let screen = self.screen.borrow();
+        (screen.width as u16, screen.height as u16)
+    }
+
+    /// The visible rows with trailing whitespace trimmed
+    pub fn screen(&self) -> Vec<String> {
+        self.screen.borrow().rows()
+    }
+
+    /// Scrollback and screen joined by newlines, without trailing empty rows
+    pub fn contents(&self) -> String {
+        let screen = self.screen.borrow();
+        let mut lines = screen.scrollback.clone();
+        lines.extend(screen.rows());
+        while lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
+    /// Cursor position as (column, row), zero based
+    pub fn cursor(&self) -> (u16, u16) {
+        let screen = self.screen.borrow();
+        (screen.col as u16, screen.row as u16)
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.screen.borrow().cursor_visible
+    }
+
+    pub fn is_raw(&self) -> bool {
+        self.screen.borrow().raw
+    }
+
+    /// Pretend to be a pipe rather than a tty
+    pub fn set_interactive(&self, interactive: bool) {
+        self.screen.borrow_mut().interactive = interactive;
+    }
+}
+
+impl Write for VirtualTerminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.screen.borrow_mut().write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Terminal for VirtualTerminal {
+    fn is_interactive(&self) -> bool {
+        self.screen.borrow().interactive
+    }
+
+    fn set_raw_mode(&mut self, raw: bool) -> io::Result<bool> {
+        Ok(std::mem::replace(&mut self.screen.borrow_mut().raw, raw))
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(VirtualTerminal::size(self))
+    }
+
+    fn poll_input(&self, _timeout: Duration) -> io::Result<bool> {
+        Ok(self.script.borrow_mut().poll())
+    }
+}
+
+/// Keys typed into a [`VirtualTerminal`]
+#[derive(Debug)]
+pub struct VirtualInput {
+    script: Rc<RefCell<Script>>,
+    screen: Rc<RefCell<Screen>>,
+}
+
+impl VirtualInput {
+    /// Whether every key has been read
+    pub fn is_empty(&self) -> bool {
+        self.script.borrow().is_empty()
+    }
+}
+
+impl Read for VirtualInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.script.borrow_mut().read(buf)?;
+        let mut screen = self.screen.borrow_mut();
+        if screen.interactive && !screen.raw {
+            for b in &buf[..n] {
+                match b {
+                    0x7f => screen.write(b"\x08 \x08"),
+                    b'\n' | b'\r' => screen.write(b"\n"),
+                    b if *b >= 0x20 => screen.write(&[*b]),
+                    _ => {}
+                }
+            }
+        }
+        Ok(n)
+    }
+}
+
+#[derive(Debug)]
+struct Screen {
+    width: usize,
+    height: usize,
+    grid: Vec<Vec<char>>,
+    scrollback: Vec<String>,
+    row: usize,
+    col: usize,
+    saved: (usize, usize),
+    raw: bool,
+    interactive: bool,
+    cursor_visible: bool,
+    pending: Vec<u8>,
+    state: State,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum State {
+    Ground,
+    Escape,
+    Csi(String),
+}
+
+impl Screen {
+    fn new(width: u16, height: u16) -> Screen {
+        let width = std::cmp::max(width as usize, 1);
+        let height = std::cmp::max(height as usize, 1);
+        Screen {
+            width,
+            height,
+            grid: vec![vec![' '; width]; height],
+            scrollback: Vec::new(),
+            row: 0,
+            col: 0,
+            saved: (0, 0),
+            raw: false,
+            interactive: true,
+            cursor_visible: true,
+            pending: Vec::new(),
+            state: State::Ground,
+        }
+    }
+
+    fn rows(&self) -> Vec<String> {
+        self.grid.iter().map(|row| line(row)).collect()
+    }
+
+    fn feed(&mut self, c: char) {
+        let state = std::mem::replace(&mut self.state, State::Ground);
+        self.state = match state {
+            State::Ground => {
+                self.ground(c);
+                if c == '\x1b' {
+                    State::Escape
+                } else {
+                    State::Ground
+                }
+            }
+            State::Escape => match c {
+                '[' => State::Csi(String::new()),
+                '7' => {
+                    self.saved = (self.row, self.col);
+                    State::Ground
+                }
+                '8' => {
+                    let (row, col) = self.saved;
+                    self.row = row;
+                    self.col = col;
+                    State::Ground
+                }
+                _ => State::Ground,
+            },
+            State::Csi(mut params) => {
+                if ('\x40'..='\x7e').contains(&c) {
+                    self.csi(&params, c);
+                    State::Ground
+                } else {
+                    params.push(c);
+                    State::Csi(params)
+                }
+            }
+        };
+    }
+
+    fn ground(&mut self, c: char) {
+        match c {
+            '\x1b' => {}
+            '\r' => self.col = 0,
+            '\n' => {
+                // Without raw mode the tty translates newlines to CRLF
+                if !self.raw {
+                    self.col = 0;
+                }
+                self.line_feed();
+            }
+            '\x08' => self.col = self.col.saturating_sub(1),
+            '\t' => self.col = std::cmp::min((self.col / 8 + 1) * 8, self.width - 1),
+            c if c.is_control() => {}
+            c => {
+                // Combining marks are dropped, wide chars fill a second cell
+                // with WIDE_TAIL
+                let width = c.width().unwrap_or(0);
+                if width == 0 {
+                    return;
+                }
+                if self.col + width > self.width {
+                    self.col = 0;
+                    self.line_feed();
+                }
+                self.grid[self.row][self.col] = c;
+                if width == 2 {
+                    self.grid[self.row][self.col + 1] = WIDE_TAIL;
+                }
+                self.col += width;
+            }
+        }
+    }
+
+    fn csi(&mut self, params: &str, action: char) {
+        let private = params.starts_with('?');
+        let args = params
+            .trim_start_matches('?')
+            .split(';')
+            .map(|p| p.parse::<usize>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let arg = |i: usize| args.get(i).cloned().unwrap_or(0);
+        // Movement by zero moves by one, like a real terminal
+        let n = std::cmp::max(arg(0), 1);
+
+        match action {
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' => self.row = std::cmp::min(self.row + n, self.height - 1),
+            'C' => self.col = std::cmp::min(self.col + n, self.width - 1),
+            'D' => self.col = std::cmp::min(self.col, self.width - 1).saturating_sub(n),
+            'G' => self.col = std::cmp::min(n, self.width) - 1,
+            'H' | 'f' => {
+                self.row = std::cmp::min(std::cmp::max(arg(0), 1), self.height) - 1;
+                self.col = std::cmp::min(std::cmp::max(arg(1), 1), self.width) - 1;
+            }
+            'J' => {
+                let (row, col) = (self.row, std::cmp::min(self.col, self.width));
+                match arg(0) {
+                    0 => {
+                        self.clear(row, col..self.width);
+                        for r in row + 1..self.height {
+                            self.clear(r, 0..self.width);
+                        }
+                    }
+                    1 => {
+                        for r in 0..row {
+                            self.clear(r, 0..self.width);
+                        }
+                        self.clear(row, 0..std::cmp::min(col + 1, self.width));
+                    }
+                    _ => {
+                        for r in 0..self.height {
+                            self.clear(r, 0..self.width);
+                        }
+                    }
+                }
+            }
+            'K' => {
+                let (row, col) = (self.row, std::cmp::min(self.col, self.width));
+                match arg(0) {
+                    0 => self.clear(row, col..self.width),
+                    1 => self.clear(row, 0..std::cmp::min(col + 1, self.width)),
+                    _ => self.clear(row, 0..self.width),
+                }
+            }
+            's' => self.saved = (self.row, self.col),
+            'u' => {
+                let (row, col) = self.saved;
+                self.row = row;
+                self.col = col;
+            }
+            'h' if private && arg(0) == 25 => self.cursor_visible = true,
+            'l' if private && arg(0) == 25 => self.cursor_visible = false,
+            _ => {}
+        }
+    }
+
+    fn clear(&mut self, row: usize, cols: std::ops::Range<usize>) {
+        for cell in &mut self.grid[row][cols] {
+            *cell = ' ';
+        }
+    }
+
+    fn line_feed(&mut self) {
+        if self.row + 1 < self.height {
+            self.row += 1;
+        } else {
+            let top = self.grid.remove(0);
+            self.scrollback.push(line(&top));
+            self.grid.push(vec![' '; self.width]);
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) {
+        self.pending.extend_from_slice(buf);
+
+        let pending = std::mem::take(&mut self.pending);
+        let mut rest = &pending[..];
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(s) => {
+                    s.chars().for_each(|c| self.feed(c));
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    std::str::from_utf8(valid)
+                        .unwrap_or_default()
+                        .chars()
+                        .for_each(|c| self.feed(c));
+                    match e.error_len() {
+                        Some(len) => {
+                            self.feed(std::char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            // Incomplete sequence, wait for more bytes
+                            self.pending = after.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Second cell of a wide char
+const WIDE_TAIL: char = '\0';
+
+fn line(row: &[char]) -> String {
+    row.iter()
+        .filter(|c| **c != WIDE_TAIL)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
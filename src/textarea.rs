@@ -0,0 +1,357 @@
+use super::editor::{print_answer, read_text, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::line::{wrap, LineBuffer, Row};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::{Read, Write};
+use termion::{clear, cursor, event::Key, input::TermRead};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use valid::{Valid, Validation};
+
+/// Key that submits a `TextArea`, Enter inserts a newline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Submit {
+    CtrlD,
+    AltEnter,
+}
+
+impl Submit {
+    fn matches(self, key: Key) -> bool {
+        match self {
+            Submit::CtrlD => key == Key::Ctrl('d'),
+            Submit::AltEnter => key == Key::Alt('\r') || key == Key::Alt('\n'),
+        }
+    }
+}
+
+pub struct TextAreaBuilder<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<&'a str>,
+    height: usize,
+    submit: Submit,
+    validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+}
+
+impl<'a> TextAreaBuilder<'a> {
+    pub fn new(msg: &'a str) -> TextAreaBuilder<'a> {
+        TextAreaBuilder {
+            msg,
+            name: None,
+            theme: None,
+            default: None,
+            height: 6,
+            submit: Submit::CtrlD,
+            validations: None,
+        }
+    }
+
+    pub fn validate<V: Validation<String> + 'static>(mut self, v: V) -> Self {
+        if let Some(valid) = self.validations.take() {
+            self.validations = Some(valid.and_boxed(v));
+        } else {
+            self.validations = Some(Valid::new(Box::new(v)));
+        }
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> TextAreaBuilder<'a> {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Text the editor starts with
+    pub fn default(mut self, default: &'a str) -> TextAreaBuilder<'a> {
+        self.default = Some(default);
+        self
+    }
+
+    /// Number of rows shown, longer text scrolls
+    pub fn height(mut self, height: usize) -> TextAreaBuilder<'a> {
+        self.height = std::cmp::max(height, 1);
+        self
+    }
+
+    pub fn submit(mut self, submit: Submit) -> TextAreaBuilder<'a> {
+        self.submit = submit;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> TextAreaBuilder<'a> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> TextArea<'a> {
+        TextArea {
+            msg: self.msg,
+            name: self.name,
+            theme: self.theme,
+            default: self.default,
+            height: self.height,
+            submit: self.submit,
+            validations: self.validations,
+        }
+    }
+}
+
+impl<'a> IntoEditor for TextAreaBuilder<'a> {
+    type Editor = TextArea<'a>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Multi-line text, soft wrapped to the terminal width.
+///
+/// When not interactive the text is read up to a line with a single `.`,
+/// or to the end of the input.
+pub struct TextArea<'a> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    theme: Option<Theme>,
+    default: Option<&'a str>,
+    height: usize,
+    submit: Submit,
+    validations: Option<Valid<Box<dyn Validation<String>>, String>>,
+}
+
+/// `(3 lines)`, counting a line after a trailing newline like the rows
+/// of the editor do
+pub(crate) fn summary(text: &str) -> String {
+    if text.is_empty() {
+        return "(empty)".to_string();
+    }
+    match text.split('\n').count() {
+        1 => "(1 line)".to_string(),
+        n => format!("({} lines)", n),
+    }
+}
+
+impl<'a> TextArea<'a> {
+    pub fn new(msg: &'a str) -> TextAreaBuilder<'a> {
+        TextAreaBuilder::new(msg)
+    }
+
+    pub fn run(&self) -> Result<String> {
+        <TextArea as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn validate(&self, text: &str) -> std::result::Result<(), String> {
+        match &self.validations {
+            Some(v) => v.validate(&text.to_owned()).map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Move the cursor `down` rows or up, keeping its column
+    fn move_vertical(&self, buffer: &mut LineBuffer, rows: &[Row], down: bool) {
+        let cur = match rows.iter().position(|r| r.contains(buffer.cursor())) {
+            Some(cur) => cur,
+            None => return,
+        };
+        let target = match (down, cur) {
+            (true, cur) if cur + 1 < rows.len() => rows[cur + 1],
+            (false, cur) if cur > 0 => rows[cur - 1],
+            _ => return,
+        };
+
+        let text = buffer.text();
+        let col = text[rows[cur].start..buffer.cursor()].width();
+        let mut pos = target.start;
+        let mut w = 0;
+        for (i, g) in text[target.start..target.end].grapheme_indices(true) {
+            if w + g.width() > col {
+                break;
+            }
+            w += g.width();
+            pos = target.start + i + g.len();
+        }
+        // The end of a row that isn't the last of its line is drawn on the
+        // next row
+        if pos == target.end && !target.last {
+            pos = text[target.start..target.end]
+                .grapheme_indices(true)
+                .next_back()
+                .map(|(i, _)| target.start + i)
+                .unwrap_or(target.start);
+        }
+        buffer.set_cursor(pos);
+    }
+
+    fn line_start(buffer: &LineBuffer) -> usize {
+        buffer.text()[..buffer.cursor()]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0)
+    }
+
+    fn line_end(buffer: &LineBuffer) -> usize {
+        buffer.text()[buffer.cursor()..]
+            .find('\n')
+            .map(|i| buffer.cursor() + i)
+            .unwrap_or_else(|| buffer.text().len())
+    }
+
+    /// Draw the question, the visible rows and the error line, leaving the
+    /// cursor in the text. Returns the row of the cursor below the question.
+    fn render<W: Terminal>(
+        &self,
+        stdout: &mut W,
+        theme: &Theme,
+        buffer: &LineBuffer,
+        rows: &[Row],
+        offset: usize,
+        error: Option<&str>,
+    ) -> Result<usize> {
+        theme.print_question(stdout, self.msg, None)?;
+        for row in rows.iter().skip(offset).take(self.height) {
+            write!(
+                stdout,
+                "\r\n{}{}",
+                clear::CurrentLine,
+                &buffer.text()[row.start..row.end]
+            )?;
+        }
+        let shown = std::cmp::min(rows.len().saturating_sub(offset), self.height);
+        for _ in shown..self.height {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        }
+        write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        if let Some(error) = error {
+            theme.print_error(stdout, error)?;
+        }
+
+        let cur = rows
+            .iter()
+            .position(|r| r.contains(buffer.cursor()))
+            .unwrap_or(0);
+        let row = cur - offset + 1;
+        let col = buffer.text()[rows[cur].start..buffer.cursor()].width();
+        write!(stdout, "{}\r", cursor::Up((self.height + 1 - row) as u16))?;
+        if col > 0 {
+            write!(stdout, "{}", cursor::Right(col as u16))?;
+        }
+        stdout.flush()?;
+        Ok(row)
+    }
+
+    /// Clear everything drawn below the question, leaving the cursor on it
+    fn clear<W: Terminal>(&self, stdout: &mut W, row: usize) -> Result<()> {
+        if row > 0 {
+            write!(stdout, "{}", cursor::Up(row as u16))?;
+        }
+        for _ in 0..=self.height {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        }
+        write!(stdout, "{}\r", cursor::Up(self.height as u16 + 1))?;
+        Ok(())
+    }
+}
+
+impl<'a> Editor for TextArea<'a> {
+    type Output = String;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_text(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let width = std::cmp::max(stdout.size()?.0 as usize, 1);
+        let mut stdout = RawMode::new(stdout)?;
+        let mut keys = stdin.keys();
+        let mut buffer = LineBuffer::default();
+        if let Some(default) = self.default {
+            buffer.set(default);
+        }
+        let mut error: Option<String> = None;
+        let mut offset = 0;
+        let mut row = 0;
+
+        loop {
+            let rows = wrap(buffer.text(), width);
+            let cur = rows
+                .iter()
+                .position(|r| r.contains(buffer.cursor()))
+                .unwrap_or(0);
+            if cur < offset {
+                offset = cur;
+            } else if cur >= offset + self.height {
+                offset = cur + 1 - self.height;
+            }
+
+            if row > 0 {
+                write!(stdout, "{}", cursor::Up(row as u16))?;
+            }
+            row = self.render(&mut stdout, theme, &buffer, &rows, offset, error.as_deref())?;
+
+            let key = match keys.next() {
+                Some(key) => key?,
+                None => return Err(Error::NoMoreInput),
+            };
+
+            match key {
+                key if self.submit.matches(key) => match self.validate(buffer.text()) {
+                    Ok(()) => break,
+                    Err(err) => error = Some(err),
+                },
+                Key::Ctrl('c') => {
+                    self.clear(&mut stdout, row)?;
+                    write!(stdout, "\n\r")?;
+                    return Err(Error::UserAborted);
+                }
+                Key::Char('\n') => buffer.insert('\n'),
+                Key::Char('\t') => buffer.insert_str("    "),
+                Key::Up => self.move_vertical(&mut buffer, &rows, false),
+                Key::Down => self.move_vertical(&mut buffer, &rows, true),
+                Key::Home | Key::Ctrl('a') => buffer.set_cursor(Self::line_start(&buffer)),
+                Key::End | Key::Ctrl('e') => buffer.set_cursor(Self::line_end(&buffer)),
+                Key::Ctrl('u') => buffer.delete_range(Self::line_start(&buffer), buffer.cursor()),
+                Key::Ctrl('k') => buffer.delete_range(buffer.cursor(), Self::line_end(&buffer)),
+                key => {
+                    buffer.edit(key);
+                }
+            }
+        }
+
+        self.clear(&mut stdout, row)?;
+        let text = buffer.text().to_owned();
+        theme.print_results(&mut stdout, self.msg, &summary(&text))?;
+        Ok(text)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<String> {
+        let text = match (answer, self.default) {
+            (Some(answer), _) => answer.to_owned(),
+            (None, Some(default)) => default.to_owned(),
+            (None, None) => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        self.validate(&text)
+            .map_err(|err| Error::InvalidAnswer(self.msg.to_string(), err))?;
+        print_answer(stdout, theme, self.msg, &summary(&text))?;
+        Ok(text)
+    }
+}
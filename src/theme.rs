@@ -1,4 +1,7 @@
 use super::choice::Choice;
+use super::date::Time;
+use super::datepicker::CalendarDay;
+use super::tree::Check;
 use std::borrow::Cow;
 use std::fmt::{self};
 use std::io::{self, Write};
@@ -224,8 +227,20 @@ pub struct Theme {
     highlight: Style,
     selected: Style,
     result: Style,
+    matched: Style,
+    suggestion: Style,
+    today: Style,
+    selected_day: Style,
+    disabled: Style,
+    track: Style,
+    fill: Style,
+    handle: Style,
+    divider: Style,
+    reason: Style,
+    hint: Style,
     highlight_indicator: Indicator,
     selected_indicator: Indicator,
+    partial_indicator: String,
 }
 
 impl Theme {
@@ -249,7 +264,7 @@ impl Theme {
             .prefix()
             .plain(msg);
         if let Some(default) = default {
-            line = line.styled(
+            line = line.plain(" ").styled(
                 Style::default().fg(Color::LightBlue),
                 format!("[{}]", default),
             );
@@ -278,13 +293,26 @@ impl Theme {
         Ok(self)
     }
 
+    /// Like `print_results`, without colors or cursor movement
+    pub fn print_plain_results(
+        &self,
+        writer: &mut dyn Write,
+        msg: &str,
+        ans: &str,
+    ) -> Result<&Theme, io::Error> {
+        let prefix = normalize(self.prefix.as_deref().unwrap_or(""))?;
+        writer.write_all(&prefix)?;
+        writeln!(writer, "{}{}{}", msg, self.separator, ans)?;
+        Ok(self)
+    }
+
     pub fn print_choice<R: Write, C: Choice>(
         &self,
         output: &mut R,
         choice: &C,
         highlighted: bool,
     ) -> Result<&Theme, io::Error> {
-        let prefix = normalize(self.prefix.as_ref().map(|m| m.as_str()).unwrap_or(""))?;
+        let prefix = normalize(self.prefix.as_deref().unwrap_or(""))?;
         let space = String::from_iter((0..prefix.len()).map(|_| ' '));
         let choice = choice.text().to_string();
         let mut line = self
@@ -305,6 +333,148 @@ impl Theme {
         Ok(self)
     }
 
+    /// Print a node of a tree prompt. `expanded` is `None` for leaves and
+    /// `check` is `None` when only one node can be selected.
+    pub fn print_tree_node<R: Write, C: Choice>(
+        &self,
+        output: &mut R,
+        choice: &C,
+        depth: usize,
+        expanded: Option<bool>,
+        highlighted: bool,
+        check: Option<Check>,
+    ) -> Result<&Theme, io::Error> {
+        let space = self.indent()?;
+        let nesting = String::from_iter((0..depth * 2).map(|_| ' '));
+        let choice = choice.text().to_string();
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .highlight_indicator(highlighted)
+            .plain(" ");
+
+        if let Some(check) = check {
+            line = line.check_indicator(check).plain(" ");
+        }
+
+        line = line.plain(&nesting).plain(match expanded {
+            Some(true) => "▾ ",
+            Some(false) => "▸ ",
+            None => "  ",
+        });
+
+        line = if highlighted && check != Some(Check::Checked) {
+            line.highlight(&choice)
+        } else if check == Some(Check::Checked) {
+            line.styled(self.selected, &choice)
+        } else {
+            line.plain(&choice)
+        };
+
+        write!(output, "{}", line)?;
+
+        Ok(self)
+    }
+
+    /// Print a choice of a `Sort` prompt with its position, the grabbed
+    /// choice is styled as selected
+    pub fn print_ranked_choice<R: Write, C: Choice>(
+        &self,
+        output: &mut R,
+        rank: usize,
+        choice: &C,
+        highlighted: bool,
+        grabbed: bool,
+    ) -> Result<&Theme, io::Error> {
+        let space = self.indent()?;
+        let rank = format!("{}. ", rank);
+        let choice = choice.text().to_string();
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .highlight_indicator(highlighted)
+            .plain(" ")
+            .plain(&rank);
+
+        line = if grabbed {
+            line.styled(self.selected, &choice)
+        } else if highlighted {
+            line.highlight(&choice)
+        } else {
+            line.plain(&choice)
+        };
+
+        write!(output, "{}", line)?;
+
+        Ok(self)
+    }
+
+    /// Print a choice of an expanded `Expand` prompt, as `y) Yes`
+    pub fn print_keyed_choice<R: Write>(
+        &self,
+        output: &mut R,
+        key: char,
+        text: &str,
+        default: bool,
+    ) -> Result<&Theme, io::Error> {
+        let space = self.indent()?;
+        let key = format!("{}) ", key);
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .plain("  ");
+
+        line = if default {
+            line.highlight(&key).highlight(text)
+        } else {
+            line.plain(&key).plain(text)
+        };
+
+        write!(output, "{}", line)?;
+
+        Ok(self)
+    }
+
+    /// Print a choice where the chars at `matches` are styled as matched
+    pub fn print_filtered_choice<R: Write, C: Choice>(
+        &self,
+        output: &mut R,
+        choice: &C,
+        highlighted: bool,
+        matches: &[usize],
+    ) -> Result<&Theme, io::Error> {
+        let prefix = normalize(self.prefix.as_deref().unwrap_or(""))?;
+        let space = String::from_iter((0..prefix.len()).map(|_| ' '));
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .highlight_indicator(highlighted)
+            .plain(" ");
+
+        let base = if highlighted {
+            self.highlight
+        } else {
+            self.default
+        };
+
+        for (i, c) in choice.text().to_string().chars().enumerate() {
+            let style = if matches.contains(&i) {
+                self.matched
+            } else {
+                base
+            };
+            line = line.styled(style, c.to_string());
+        }
+
+        write!(output, "{}", line)?;
+
+        Ok(self)
+    }
+
     pub fn print_error(&self, output: &mut dyn Write, error: &str) -> Result<(), io::Error> {
         let line = self
             .builder()
@@ -315,6 +485,224 @@ impl Theme {
         write!(output, "{}", line)
     }
 
+    /// Print the part of a suggestion after the typed text
+    pub fn print_suggestion(&self, output: &mut dyn Write, rest: &str) -> Result<(), io::Error> {
+        write!(output, "{}", StyledString::new(self.suggestion, rest))
+    }
+
+    /// Print instructions after a question
+    pub fn print_hint(&self, output: &mut dyn Write, hint: &str) -> Result<(), io::Error> {
+        write!(output, "{}", StyledString::new(self.hint, hint))
+    }
+
+    /// Print a muted row standing in for choices, like while they load
+    pub fn print_placeholder(&self, output: &mut dyn Write, text: &str) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .plain("  ")
+            .styled(self.disabled, text);
+        write!(output, "{}", line)
+    }
+
+    /// Print a reverse history search, with the query styled as matched in
+    /// the entry found
+    pub fn print_history_search(
+        &self,
+        output: &mut dyn Write,
+        query: &str,
+        entry: Option<&str>,
+    ) -> Result<(), io::Error> {
+        let label = match entry {
+            None if !query.is_empty() => "(failed reverse-i-search)",
+            _ => "(reverse-i-search)",
+        };
+        let mut line = self
+            .builder()
+            .styled(Style::default().fg(Color::LightBlack), label)
+            .plain("`")
+            .plain(query)
+            .plain("': ");
+
+        if let Some(entry) = entry {
+            let (start, end) = match entry.find(query) {
+                Some(start) if !query.is_empty() => (start, start + query.len()),
+                _ => (entry.len(), entry.len()),
+            };
+            line = line
+                .styled(self.default, &entry[..start])
+                .styled(self.matched, &entry[start..end])
+                .styled(self.default, &entry[end..]);
+        }
+
+        write!(output, "{}", line)
+    }
+
+    /// Print the month shown by a `DatePicker`, centered over its days
+    pub fn print_calendar_title(
+        &self,
+        output: &mut dyn Write,
+        title: &str,
+    ) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let pad = String::from_iter((0..20usize.saturating_sub(title.len()) / 2).map(|_| ' '));
+        let line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .plain(&pad)
+            .highlight(title);
+        write!(output, "{}", line)
+    }
+
+    /// Print the names of the days of the week, starting on Monday
+    pub fn print_weekdays(&self, output: &mut dyn Write) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .styled(self.disabled, "Mo Tu We Th Fr Sa Su");
+        write!(output, "{}", line)
+    }
+
+    /// Print a week of a `DatePicker`, `None` are days outside the month
+    pub fn print_week(
+        &self,
+        output: &mut dyn Write,
+        days: &[Option<CalendarDay>],
+    ) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space);
+
+        for (i, day) in days.iter().enumerate() {
+            if i > 0 {
+                line = line.plain(" ");
+            }
+            line = match day {
+                None => line.plain("  "),
+                Some(day) => {
+                    let style = if day.selected {
+                        self.selected_day
+                    } else if day.disabled {
+                        self.disabled
+                    } else if day.today {
+                        self.today
+                    } else {
+                        self.default
+                    };
+                    line.styled(style, format!("{:>2}", day.day))
+                }
+            };
+        }
+
+        write!(output, "{}", line)
+    }
+
+    /// Print a slider `width` cells wide with the handle at `handle`, between
+    /// optional labels
+    pub fn print_slider(
+        &self,
+        output: &mut dyn Write,
+        handle: usize,
+        width: usize,
+        labels: Option<(&str, &str)>,
+    ) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let fill = String::from_iter((0..handle).map(|_| '━'));
+        let track = String::from_iter((handle + 1..width).map(|_| '─'));
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space);
+
+        if let Some((left, _)) = labels {
+            line = line.plain(left).plain(" ");
+        }
+        line = line
+            .styled(self.fill, fill)
+            .styled(self.handle, "●")
+            .styled(self.track, track);
+        if let Some((_, right)) = labels {
+            line = line.plain(" ").plain(right);
+        }
+
+        write!(output, "{}", line)
+    }
+
+    /// Print the time picked by a `DatePicker`, with the focused field
+    /// highlighted
+    pub fn print_time(
+        &self,
+        output: &mut dyn Write,
+        time: Time,
+        hour_focused: bool,
+        minute_focused: bool,
+    ) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let style = |focused| {
+            if focused {
+                self.selected_day
+            } else {
+                self.default
+            }
+        };
+        let line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .plain("Time ")
+            .styled(style(hour_focused), format!("{:02}", time.hour()))
+            .plain(":")
+            .styled(style(minute_focused), format!("{:02}", time.minute()));
+        write!(output, "{}", line)
+    }
+
+    /// Print a separator as a heading, or a disabled choice with the reason
+    /// it cannot be picked. `check` leaves room for the selected indicator.
+    pub fn print_unselectable<R: Write, C: Choice>(
+        &self,
+        output: &mut R,
+        choice: &C,
+        check: bool,
+    ) -> Result<(), io::Error> {
+        let space = self.indent()?;
+        let text = choice.text().to_string();
+        let mut line = self
+            .builder()
+            .plain(clear::CurrentLine.as_ref())
+            .plain(&space)
+            .highlight_indicator(false)
+            .plain(" ");
+
+        if choice.is_separator() {
+            line = line.styled(self.divider, text);
+        } else {
+            if check {
+                line = line
+                    .styled(self.disabled, self.selected_indicator.inactive.as_str())
+                    .plain(" ");
+            }
+            line = line.styled(self.disabled, text);
+            if let Some(reason) = choice.disabled().filter(|r| !r.is_empty()) {
+                line = line.plain(" ").styled(self.reason, format!("({})", reason));
+            }
+        }
+
+        write!(output, "{}", line)
+    }
+
+    /// Spaces as wide as the prefix, to line rows up with the question
+    fn indent(&self) -> Result<String, io::Error> {
+        let prefix = normalize(self.prefix.as_deref().unwrap_or(""))?;
+        Ok(String::from_iter((0..prefix.len()).map(|_| ' ')))
+    }
+
     pub fn print_multiple_choice<R: Write, C: Choice>(
         &self,
         output: &mut R,
@@ -322,7 +710,7 @@ impl Theme {
         highlighted: bool,
         selected: bool,
     ) -> Result<&Theme, io::Error> {
-        let prefix = normalize(self.prefix.as_ref().map(|m| m.as_str()).unwrap_or(""))?;
+        let prefix = normalize(self.prefix.as_deref().unwrap_or(""))?;
         let space = String::from_iter((0..prefix.len()).map(|_| ' '));
         let choice = choice.text().to_string();
         let mut line = self
@@ -357,8 +745,20 @@ pub struct ThemeBuilder {
     highlight: Style,
     selected: Style,
     result: Style,
+    matched: Style,
+    suggestion: Style,
+    today: Style,
+    selected_day: Style,
+    disabled: Style,
+    track: Style,
+    fill: Style,
+    handle: Style,
+    divider: Style,
+    reason: Style,
+    hint: Style,
     highlight_indicator: Option<Indicator>,
     selected_indicator: Option<Indicator>,
+    partial_indicator: Option<String>,
 }
 
 impl Default for ThemeBuilder {
@@ -371,8 +771,28 @@ impl Default for ThemeBuilder {
             result: Style::default().fg(Color::Cyan),
             highlight: Style::default().fg(Color::Cyan),
             selected: Style::default().fg(Color::Green),
+            matched: Style::default().fg(Foreground::default()
+                .color(Color::Yellow)
+                .decoration(Decoration::Bold)),
+            suggestion: Style::default().fg(Color::LightBlack),
+            today: Style::default().fg(Decoration::Underline),
+            selected_day: Style::default().fg(Foreground::default()
+                .color(Color::Cyan)
+                .decoration(Decoration::Invert)),
+            disabled: Style::default().fg(Color::LightBlack),
+            track: Style::default().fg(Color::LightBlack),
+            fill: Style::default().fg(Color::Cyan),
+            handle: Style::default().fg(Foreground::default()
+                .color(Color::Cyan)
+                .decoration(Decoration::Bold)),
+            divider: Style::default().fg(Decoration::Bold),
+            reason: Style::default().fg(Foreground::default()
+                .color(Color::LightBlack)
+                .decoration(Decoration::Italic)),
+            hint: Style::default().fg(Color::LightBlack),
             highlight_indicator: None,
             selected_indicator: None,
+            partial_indicator: None,
         }
     }
 }
@@ -413,6 +833,67 @@ impl ThemeBuilder {
         self
     }
 
+    pub fn matched(mut self, style: impl Into<Style>) -> Self {
+        self.matched = style.into();
+        self
+    }
+
+    pub fn suggestion(mut self, style: impl Into<Style>) -> Self {
+        self.suggestion = style.into();
+        self
+    }
+
+    pub fn today(mut self, style: impl Into<Style>) -> Self {
+        self.today = style.into();
+        self
+    }
+
+    pub fn selected_day(mut self, style: impl Into<Style>) -> Self {
+        self.selected_day = style.into();
+        self
+    }
+
+    /// Days out of range and choices that cannot be picked
+    pub fn disabled(mut self, style: impl Into<Style>) -> Self {
+        self.disabled = style.into();
+        self
+    }
+
+    /// Part of a slider after the handle
+    pub fn track(mut self, style: impl Into<Style>) -> Self {
+        self.track = style.into();
+        self
+    }
+
+    /// Part of a slider before the handle
+    pub fn fill(mut self, style: impl Into<Style>) -> Self {
+        self.fill = style.into();
+        self
+    }
+
+    pub fn handle(mut self, style: impl Into<Style>) -> Self {
+        self.handle = style.into();
+        self
+    }
+
+    /// Separator rows between groups of choices
+    pub fn divider(mut self, style: impl Into<Style>) -> Self {
+        self.divider = style.into();
+        self
+    }
+
+    /// Reason a disabled choice cannot be picked
+    pub fn reason(mut self, style: impl Into<Style>) -> Self {
+        self.reason = style.into();
+        self
+    }
+
+    /// Instructions shown after a question, like how to open an editor
+    pub fn hint(mut self, style: impl Into<Style>) -> Self {
+        self.hint = style.into();
+        self
+    }
+
     pub fn highlight_indicator(mut self, indicator: impl Into<Indicator>) -> Self {
         self.highlight_indicator = Some(indicator.into());
         self
@@ -422,6 +903,12 @@ impl ThemeBuilder {
         self.selected_indicator = Some(indicator.into());
         self
     }
+
+    /// Shown for tree nodes where only some children are selected
+    pub fn partial_indicator(mut self, indicator: impl ToString) -> Self {
+        self.partial_indicator = Some(indicator.to_string());
+        self
+    }
 }
 
 impl ThemeBuilder {
@@ -434,6 +921,17 @@ impl ThemeBuilder {
             highlight: self.highlight,
             selected: self.selected,
             result: self.result,
+            matched: self.matched,
+            suggestion: self.suggestion,
+            today: self.today,
+            selected_day: self.selected_day,
+            disabled: self.disabled,
+            track: self.track,
+            fill: self.fill,
+            handle: self.handle,
+            divider: self.divider,
+            reason: self.reason,
+            hint: self.hint,
             highlight_indicator: self.highlight_indicator.unwrap_or_else(|| Indicator {
                 active: "❯".to_string(),
                 inactive: " ".to_string(),
@@ -442,6 +940,7 @@ impl ThemeBuilder {
                 active: "◉".to_string(),
                 inactive: "◯".to_string(),
             }),
+            partial_indicator: self.partial_indicator.unwrap_or_else(|| "◐".to_string()),
         }
     }
 }
@@ -475,6 +974,19 @@ impl<'a> LineBuilder<'a> {
         self
     }
 
+    /// The selected indicator, or the partial one for `Check::Partial`
+    pub fn check_indicator(mut self, check: Check) -> Self {
+        match check {
+            Check::Partial => self.writer.push(StyledString::new(
+                self.theme.selected,
+                self.theme.partial_indicator.as_str(),
+            )),
+            Check::Checked => return self.selected_indicator(true),
+            Check::Unchecked => return self.selected_indicator(false),
+        }
+        self
+    }
+
     pub fn highlight_indicator(mut self, on: bool) -> Self {
         self.writer.push(StyledString::new(
             if on {
@@ -0,0 +1,587 @@
+use super::choice::Choice;
+use super::editor::{print_answer, read_answer, Editor, IntoEditor};
+use super::error::{Error, Result};
+use super::terminal::{RawMode, StdinWrap, StdoutWrap, Terminal};
+use super::theme::{Theme, DEFAULT_THEME};
+use std::io::Read;
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::{clear, cursor};
+
+/// Nodes of a tree prompt, loaded as they are expanded
+pub trait TreeSource {
+    type Node: Choice + Clone;
+
+    fn roots(&self) -> Vec<Self::Node>;
+
+    fn children(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    /// Whether `node` can be expanded, asked before loading its children.
+    /// A node without children becomes a leaf once expanded.
+    fn has_children(&self, _node: &Self::Node) -> bool {
+        true
+    }
+}
+
+/// State of a node's checkbox, `Partial` when only some children are
+/// selected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Check {
+    Unchecked,
+    Partial,
+    Checked,
+}
+
+struct Node<N> {
+    value: N,
+    depth: usize,
+    parent: Option<usize>,
+    /// `None` until loaded
+    children: Option<Vec<usize>>,
+    leaf: bool,
+    expanded: bool,
+    checked: bool,
+}
+
+/// The loaded part of a `TreeSource`
+struct Tree<'s, S: TreeSource> {
+    source: &'s S,
+    nodes: Vec<Node<S::Node>>,
+    roots: Vec<usize>,
+}
+
+impl<'s, S: TreeSource> Tree<'s, S> {
+    fn new(source: &'s S) -> Tree<'s, S> {
+        let mut tree = Tree {
+            source,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+        };
+        tree.roots = tree.add(source.roots(), None);
+        tree
+    }
+
+    fn add(&mut self, values: Vec<S::Node>, parent: Option<usize>) -> Vec<usize> {
+        let depth = parent.map(|p| self.nodes[p].depth + 1).unwrap_or(0);
+        // Children loaded below a checked node are checked with it
+        let checked = parent.map(|p| self.nodes[p].checked).unwrap_or(false);
+        values
+            .into_iter()
+            .map(|value| {
+                self.nodes.push(Node {
+                    leaf: !self.source.has_children(&value),
+                    value,
+                    depth,
+                    parent,
+                    children: None,
+                    expanded: false,
+                    checked,
+                });
+                self.nodes.len() - 1
+            })
+            .collect()
+    }
+
+    fn children(&mut self, idx: usize) -> Vec<usize> {
+        if self.nodes[idx].leaf {
+            return Vec::new();
+        }
+        if self.nodes[idx].children.is_none() {
+            let values = self.source.children(&self.nodes[idx].value);
+            let children = self.add(values, Some(idx));
+            self.nodes[idx].leaf = children.is_empty();
+            self.nodes[idx].children = Some(children);
+        }
+        self.nodes[idx].children.clone().unwrap_or_default()
+    }
+
+    /// Nodes shown, depth first through the expanded nodes
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut stack = self.roots.iter().rev().cloned().collect::<Vec<_>>();
+        while let Some(idx) = stack.pop() {
+            visible.push(idx);
+            let node = &self.nodes[idx];
+            if let (true, Some(children)) = (node.expanded, &node.children) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        visible
+    }
+
+    fn expanded(&self, idx: usize) -> Option<bool> {
+        let node = &self.nodes[idx];
+        if node.leaf {
+            None
+        } else {
+            Some(node.expanded)
+        }
+    }
+
+    fn check(&self, idx: usize) -> Check {
+        let node = &self.nodes[idx];
+        let children = match &node.children {
+            Some(children) if !children.is_empty() => children,
+            _ if node.checked => return Check::Checked,
+            _ => return Check::Unchecked,
+        };
+
+        let mut checks = children.iter().map(|c| self.check(*c));
+        let first = checks.next().unwrap_or(Check::Unchecked);
+        if checks.all(|c| c == first) {
+            first
+        } else {
+            Check::Partial
+        }
+    }
+
+    /// Check or uncheck `idx` and everything below it that is loaded, the
+    /// rest is checked as it loads
+    fn set_checked(&mut self, idx: usize, checked: bool) {
+        self.nodes[idx].checked = checked;
+        for child in self.nodes[idx].children.clone().unwrap_or_default() {
+            self.set_checked(child, checked);
+        }
+    }
+
+    /// Checked nodes in tree order, including the parents of fully
+    /// checked subtrees. Nodes below a checked node that were never loaded
+    /// are left out.
+    fn checked(&self) -> Vec<usize> {
+        let mut checked = Vec::new();
+        let mut stack = self.roots.iter().rev().cloned().collect::<Vec<_>>();
+        while let Some(idx) = stack.pop() {
+            if self.check(idx) == Check::Checked {
+                checked.push(idx);
+            }
+            if let Some(children) = &self.nodes[idx].children {
+                stack.extend(children.iter().rev());
+            }
+        }
+        checked
+    }
+
+    /// Find a node by the texts of its ancestors and itself, separated by
+    /// `/`
+    fn find(&mut self, path: &str) -> Option<usize> {
+        let mut candidates = self.roots.clone();
+        let mut found = None;
+        for part in path.trim().split('/').map(str::trim) {
+            let idx = *candidates
+                .iter()
+                .find(|c| self.nodes[**c].value.text().to_string() == part)?;
+            found = Some(idx);
+            candidates = self.children(idx);
+        }
+        found
+    }
+
+    fn text(&self, idx: usize) -> String {
+        self.nodes[idx].value.text().to_string()
+    }
+}
+
+/// Let the user walk the tree, returning the node highlighted on Enter.
+/// Leaves the cursor on the question with everything below it cleared.
+fn interact<R: Read, W: Terminal, S: TreeSource>(
+    tree: &mut Tree<S>,
+    msg: &str,
+    page_size: usize,
+    multiple: bool,
+    stdin: &mut R,
+    stdout: &mut W,
+    theme: &Theme,
+) -> Result<Option<usize>> {
+    let mut input = stdin.keys();
+    let page_size = std::cmp::max(page_size, 1);
+    let mut cur: usize = 0;
+    let mut offset: usize = 0;
+    let mut drawn: usize = 0;
+
+    write!(stdout, "{}", cursor::Hide)?;
+
+    loop {
+        let visible = tree.visible();
+        if cur < offset {
+            offset = cur;
+        } else if cur >= offset + page_size {
+            offset = cur + 1 - page_size;
+        }
+
+        theme.print_question(stdout, msg, None)?;
+        let rows = std::cmp::min(visible.len() - offset, page_size);
+        for (i, idx) in visible.iter().skip(offset).take(rows).enumerate() {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+            let check = if multiple {
+                Some(tree.check(*idx))
+            } else {
+                None
+            };
+            theme.print_tree_node(
+                stdout,
+                &tree.nodes[*idx].value,
+                tree.nodes[*idx].depth,
+                tree.expanded(*idx),
+                offset + i == cur,
+                check,
+            )?;
+        }
+        for _ in rows..drawn {
+            write!(stdout, "\r\n{}", clear::CurrentLine)?;
+        }
+        drawn = std::cmp::max(rows, drawn);
+        if drawn > 0 {
+            write!(stdout, "{}", cursor::Up(drawn as u16))?;
+        }
+        stdout.flush()?;
+
+        let next = match input.next() {
+            Some(next) => next?,
+            None => return Err(Error::NoMoreInput),
+        };
+
+        let idx = visible.get(cur).cloned();
+        match (next, idx) {
+            (Key::Char('\n'), _) => {
+                clear_rows(stdout, drawn)?;
+                write!(stdout, "\r{}", cursor::Show)?;
+                return Ok(idx);
+            }
+            (Key::Ctrl('c'), _) => {
+                clear_rows(stdout, drawn)?;
+                write!(stdout, "\n\r{}", cursor::Show)?;
+                return Err(Error::UserAborted);
+            }
+            (Key::Up, _) if cur > 0 => cur -= 1,
+            (Key::Down, _) if cur + 1 < visible.len() => cur += 1,
+            (Key::Right, Some(idx)) => {
+                if tree.nodes[idx].expanded {
+                    if tree.nodes[idx].children.as_ref().map(Vec::len) > Some(0) {
+                        cur += 1;
+                    }
+                } else if !tree.children(idx).is_empty() {
+                    tree.nodes[idx].expanded = true;
+                }
+            }
+            (Key::Left, Some(idx)) => {
+                if tree.nodes[idx].expanded {
+                    tree.nodes[idx].expanded = false;
+                } else if let Some(parent) = tree.nodes[idx].parent {
+                    cur = visible.iter().position(|v| *v == parent).unwrap_or(cur);
+                }
+            }
+            (Key::Char(' '), Some(idx)) if multiple => {
+                let checked = tree.check(idx) != Check::Checked;
+                tree.set_checked(idx, checked);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn clear_rows<W: Terminal>(stdout: &mut W, rows: usize) -> Result<()> {
+    for _ in 0..rows {
+        write!(stdout, "\r\n{}", clear::CurrentLine)?;
+    }
+    if rows > 0 {
+        write!(stdout, "{}", cursor::Up(rows as u16))?;
+    }
+    Ok(())
+}
+
+pub struct TreeSelectBuilder<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: &'a S,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: TreeSource> TreeSelectBuilder<'a, S> {
+    pub fn new(msg: &'a str, source: &'a S) -> TreeSelectBuilder<'a, S> {
+        TreeSelectBuilder {
+            msg,
+            name: None,
+            source,
+            page_size: 8,
+            theme: None,
+        }
+    }
+
+    pub fn page_size(mut self, size: usize) -> TreeSelectBuilder<'a, S> {
+        self.page_size = size;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> TreeSelectBuilder<'a, S> {
+        self.name = Some(name);
+        self
+    }
+
+    /// Check any number of nodes with Space instead
+    pub fn multiple(self) -> TreeMultiSelectBuilder<'a, S> {
+        TreeMultiSelectBuilder {
+            msg: self.msg,
+            name: self.name,
+            source: self.source,
+            page_size: self.page_size,
+            theme: self.theme,
+        }
+    }
+
+    pub fn build(self) -> TreeSelect<'a, S> {
+        TreeSelect {
+            msg: self.msg,
+            name: self.name,
+            source: self.source,
+            page_size: self.page_size,
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'a, S: TreeSource> IntoEditor for TreeSelectBuilder<'a, S> {
+    type Editor = TreeSelect<'a, S>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Picks a node of a tree. Right expands a node, Left collapses it or moves
+/// to its parent.
+///
+/// When not interactive the answer is the path to the node, as texts
+/// separated by `/`.
+pub struct TreeSelect<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: &'a S,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: TreeSource> TreeSelect<'a, S> {
+    pub fn new(msg: &'a str, source: &'a S) -> TreeSelectBuilder<'a, S> {
+        TreeSelectBuilder::new(msg, source)
+    }
+
+    pub fn run(&self) -> Result<S::Node> {
+        <TreeSelect<'a, S> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+}
+
+impl<'a, S: TreeSource> Editor for TreeSelect<'a, S> {
+    type Output = S::Node;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        // An empty tree has nothing to pick
+        let mut tree = Tree::new(self.source);
+        if tree.nodes.is_empty() {
+            return Err(Error::NoAnswer(self.msg.to_string()));
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let idx = interact(
+            &mut tree,
+            self.msg,
+            self.page_size,
+            false,
+            stdin,
+            &mut stdout,
+            theme,
+        )?
+        .ok_or_else(|| Error::NoAnswer(self.msg.to_string()))?;
+
+        theme.print_results(&mut stdout, self.msg, &tree.text(idx))?;
+        Ok(tree.nodes[idx].value.clone())
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<S::Node> {
+        let answer = match answer {
+            Some(answer) if !answer.trim().is_empty() => answer,
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        let mut tree = Tree::new(self.source);
+        let idx = tree.find(answer).ok_or_else(|| {
+            Error::InvalidAnswer(self.msg.to_string(), format!("no node {:?}", answer))
+        })?;
+        print_answer(stdout, theme, self.msg, &tree.text(idx))?;
+        Ok(tree.nodes[idx].value.clone())
+    }
+}
+
+pub struct TreeMultiSelectBuilder<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: &'a S,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: TreeSource> TreeMultiSelectBuilder<'a, S> {
+    pub fn new(msg: &'a str, source: &'a S) -> TreeMultiSelectBuilder<'a, S> {
+        TreeSelectBuilder::new(msg, source).multiple()
+    }
+
+    pub fn page_size(mut self, size: usize) -> TreeMultiSelectBuilder<'a, S> {
+        self.page_size = size;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> TreeMultiSelectBuilder<'a, S> {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> TreeMultiSelect<'a, S> {
+        TreeMultiSelect {
+            msg: self.msg,
+            name: self.name,
+            source: self.source,
+            page_size: self.page_size,
+            theme: self.theme,
+        }
+    }
+}
+
+impl<'a, S: TreeSource> IntoEditor for TreeMultiSelectBuilder<'a, S> {
+    type Editor = TreeMultiSelect<'a, S>;
+    fn into_editor(self) -> Self::Editor {
+        self.build()
+    }
+}
+
+/// Checks any number of nodes of a tree with Space. Checking a node checks
+/// everything below it, a node with only some children checked shows the
+/// partial indicator.
+///
+/// The checked nodes are returned in tree order, a checked node that was
+/// never expanded stands for everything below it. When not interactive the
+/// answer is a comma separated list of paths, as for `TreeSelect`.
+pub struct TreeMultiSelect<'a, S> {
+    msg: &'a str,
+    name: Option<&'a str>,
+    source: &'a S,
+    page_size: usize,
+    theme: Option<Theme>,
+}
+
+impl<'a, S: TreeSource> TreeMultiSelect<'a, S> {
+    pub fn new(msg: &'a str, source: &'a S) -> TreeMultiSelectBuilder<'a, S> {
+        TreeMultiSelectBuilder::new(msg, source)
+    }
+
+    pub fn run(&self) -> Result<Vec<S::Node>> {
+        <TreeMultiSelect<'a, S> as Editor>::run(
+            self,
+            &mut StdinWrap::new(),
+            &mut StdoutWrap::new(),
+            self.theme.as_ref().unwrap_or(&DEFAULT_THEME),
+        )
+    }
+
+    fn finish<W: Terminal>(
+        &self,
+        tree: &Tree<S>,
+        stdout: &mut W,
+        theme: &Theme,
+        interactive: bool,
+    ) -> Result<Vec<S::Node>> {
+        let checked = tree.checked();
+        let summary = checked
+            .iter()
+            .map(|idx| tree.text(*idx))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if interactive {
+            theme.print_results(stdout, self.msg, &summary)?;
+        } else {
+            print_answer(stdout, theme, self.msg, &summary)?;
+        }
+
+        Ok(checked
+            .into_iter()
+            .map(|idx| tree.nodes[idx].value.clone())
+            .collect())
+    }
+}
+
+impl<'a, S: TreeSource> Editor for TreeMultiSelect<'a, S> {
+    type Output = Vec<S::Node>;
+
+    fn run<R: Read, W: Terminal>(
+        &self,
+        stdin: &mut R,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Self::Output> {
+        if !stdout.is_interactive() {
+            let answer = read_answer(stdin)?;
+            return self.answer(answer.as_deref(), stdout, theme);
+        }
+
+        let mut stdout = RawMode::new(stdout)?;
+        let mut tree = Tree::new(self.source);
+        interact(
+            &mut tree,
+            self.msg,
+            self.page_size,
+            true,
+            stdin,
+            &mut stdout,
+            theme,
+        )?;
+        self.finish(&tree, &mut stdout, theme, true)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name
+    }
+
+    fn answer<W: Terminal>(
+        &self,
+        answer: Option<&str>,
+        stdout: &mut W,
+        theme: &Theme,
+    ) -> Result<Vec<S::Node>> {
+        // An empty line is no answer, not an empty selection
+        let answer = match answer {
+            Some(answer) if !answer.trim().is_empty() => answer,
+            _ => return Err(Error::NoAnswer(self.msg.to_string())),
+        };
+
+        let mut tree = Tree::new(self.source);
+        for path in answer.split(',').filter(|p| !p.trim().is_empty()) {
+            let idx = tree.find(path).ok_or_else(|| {
+                Error::InvalidAnswer(self.msg.to_string(), format!("no node {:?}", path.trim()))
+            })?;
+            tree.set_checked(idx, true);
+        }
+        self.finish(&tree, stdout, theme, false)
+    }
+}
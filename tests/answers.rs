@@ -0,0 +1,108 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Confirm, EnvAnswers, FileAnswers, Input, MultiSelect, Select};
+use std::collections::HashMap;
+
+#[test]
+fn skips_answered_prompts() {
+    let mut answers = HashMap::new();
+    answers.insert("name", "Rasmus");
+    answers.insert("food", "Pasta");
+    answers.insert("sure", "n");
+    let choices = vec!["Pizza", "Burger", "Pasta"];
+
+    let (form, term) = session(Script::new().text("Kildevæld").enter());
+    let mut form = form.answers(answers);
+
+    let name = form.run(Input::new("Name").name("name")).unwrap();
+    let last = form.run(Input::new("Last name").name("last")).unwrap();
+    let food = form
+        .run(Select::new("Food", &choices).name("food").build())
+        .unwrap();
+    let sure = form.run(Confirm::new("Sure?").name("sure")).unwrap();
+
+    assert_eq!(name, "Rasmus");
+    assert_eq!(last, "Kildevæld");
+    assert_eq!(food, "Pasta");
+    assert!(!sure);
+    assert_eq!(
+        term.contents(),
+        "? Name Rasmus\n? Last name Kildevæld\n? Food Pasta\n? Sure? no"
+    );
+}
+
+#[test]
+fn validates_answers() {
+    let mut answers = HashMap::new();
+    answers.insert("name", "abc");
+    answers.insert("food", "Pizza,Pasta");
+    let choices = vec!["Pizza", "Burger", "Pasta"];
+
+    let (form, _) = session(Script::new());
+    let mut form = form.answers(answers);
+
+    let err = form
+        .run(Input::new("Name").name("name").validate(valid::MinLen(5)))
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(msg, _) if msg == "Name"));
+
+    let food = form
+        .run(
+            MultiSelect::new("Food", &choices)
+                .name("food")
+                .validate(valid::MinLen(2))
+                .build(),
+        )
+        .unwrap();
+    assert_eq!(food, vec!["Pizza", "Pasta"]);
+}
+
+#[test]
+fn reads_answers_from_env() {
+    let answers = EnvAnswers::new("PROMPT_TEST_");
+    assert_eq!(answers.key("user-name"), "PROMPT_TEST_USER_NAME");
+    std::env::set_var("PROMPT_TEST_USER_NAME", "Rasmus");
+
+    let (form, _) = session(Script::new());
+    let mut form = form.answers(answers);
+    let name = form.run(Input::new("Name").name("user-name")).unwrap();
+    assert_eq!(name, "Rasmus");
+}
+
+#[test]
+fn reads_answers_from_file() {
+    let answers = FileAnswers::parse("# answers\nname = Rasmus\n\nsure=yes\n");
+
+    let (form, _) = session(Script::new());
+    let mut form = form.answers(answers);
+    assert_eq!(form.run(Input::new("Name").name("name")).unwrap(), "Rasmus");
+    assert!(form
+        .run(Confirm::new("Sure?").name("sure").default(false))
+        .unwrap());
+}
+
+struct Fixed;
+
+impl prompt::Editor for Fixed {
+    type Output = String;
+
+    fn run<R: std::io::Read, W: prompt::Terminal>(
+        &self,
+        _stdin: &mut R,
+        _stdout: &mut W,
+        _theme: &prompt::Theme,
+    ) -> prompt::error::Result<String> {
+        Ok("fixed".to_string())
+    }
+}
+
+#[test]
+fn editors_without_answers_name_themselves() {
+    let (_, term) = session(Script::new());
+    let err = prompt::Editor::answer(&Fixed, Some("x"), &mut term.clone(), &prompt::Theme::new())
+        .unwrap_err();
+    assert!(matches!(err, Error::NoAnswer(name) if name.ends_with("Fixed")));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Autocomplete, Form, Theme};
+use std::cell::RefCell;
+use termion::event::Key;
+
+fn repos(query: &str) -> Vec<String> {
+    (1..=500)
+        .map(|i| format!("repo-{}", i))
+        .filter(|r| r.contains(query))
+        .collect()
+}
+
+#[test]
+fn searches_once_typing_stops() {
+    let queries = RefCell::new(Vec::new());
+    let source = |query: &str| {
+        queries.borrow_mut().push(query.to_string());
+        repos(query)
+    };
+
+    let script = Script::new().text("42").pause().key(Key::Down).enter();
+    let (mut form, term) = session(script);
+    let repo = form.run(Autocomplete::new("Repo", source)).unwrap();
+    assert_eq!(repo, "repo-142");
+    assert_eq!(*queries.borrow(), vec!["42"]);
+    assert_eq!(term.contents(), "? Repo repo-142");
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn shows_loading_while_searching() {
+    let (mut form, term) = session(Script::new().text("4").pause());
+    let screens = RefCell::new(Vec::new());
+    let source = |query: &str| {
+        screens.borrow_mut().push(term.screen());
+        repos(query)
+    };
+
+    form.run(Autocomplete::new("Repo", source).page_size(3))
+        .unwrap_err();
+    let screens = screens.borrow();
+    assert_eq!(screens[0][0], "? Repo 4");
+    assert_eq!(screens[0][1].trim(), "Loading…");
+
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "❯ repo-4");
+    assert_eq!(screen[2].trim(), "repo-14");
+    assert_eq!(screen[3].trim(), "repo-24");
+    assert_eq!(screen[4], "");
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let script = Script::new().text("4").pause().keys(vec![Key::Down; 4]);
+    let (mut form, term) = session(script);
+    form.run(Autocomplete::new("Repo", repos).page_size(3))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "repo-24");
+    assert_eq!(screen[2].trim(), "repo-34");
+    assert_eq!(screen[3].trim(), "❯ repo-40");
+
+    let script = Script::new()
+        .text("4")
+        .pause()
+        .keys(vec![Key::Down; 4])
+        .keys(vec![Key::Up; 3])
+        .enter();
+    let (mut form, _) = session(script);
+    let repo = form.run(Autocomplete::new("Repo", repos).page_size(3));
+    assert_eq!(repo.unwrap(), "repo-14");
+}
+
+#[test]
+fn searches_the_empty_query_up_front() {
+    let script = Script::new().pause().key(Key::Down).enter();
+    let (mut form, _) = session(script);
+    let repo = form.run(Autocomplete::new("Repo", repos)).unwrap();
+    assert_eq!(repo, "repo-2");
+}
+
+#[test]
+fn enter_while_loading_searches() {
+    let script = Script::new().text("499").enter().enter();
+    let (mut form, _) = session(script);
+    assert_eq!(
+        form.run(Autocomplete::new("Repo", repos)).unwrap(),
+        "repo-499"
+    );
+}
+
+#[test]
+fn shows_no_matches() {
+    let script = Script::new().text("x").pause().enter().key(Key::Backspace);
+    let (mut form, term) = session(script);
+    form.run(Autocomplete::new("Repo", repos)).unwrap_err();
+    // Backspace searches again once the script runs out
+    assert_eq!(term.screen()[1].trim(), "❯ repo-1");
+
+    let (mut form, term) = session(Script::new().text("x").pause());
+    form.run(Autocomplete::new("Repo", repos)).unwrap_err();
+    assert_eq!(term.screen()[1].trim(), "No matches");
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(Autocomplete::new("Repo", repos)).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn searches_answers_when_not_interactive() {
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        let result = Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(Autocomplete::new("Repo", repos));
+        (result, String::from_utf8(output).unwrap())
+    };
+
+    let (repo, output) = run("repo-7\n");
+    assert_eq!(repo.unwrap(), "repo-7");
+    assert_eq!(output, "? Repo repo-7\n");
+    assert!(matches!(run("").0, Err(Error::NoAnswer(_))));
+    assert!(matches!(run("repo-501\n").0, Err(Error::InvalidAnswer(..))));
+}
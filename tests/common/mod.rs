@@ -0,0 +1,8 @@
+use prompt::testing::{Script, VirtualInput, VirtualTerminal};
+use prompt::{Form, Theme};
+
+pub fn session(script: Script) -> (Form<VirtualInput, VirtualTerminal>, VirtualTerminal) {
+    let term = VirtualTerminal::new(40, 12);
+    let form = Form::new(term.input(script), term.clone(), Theme::new());
+    (form, term)
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::session;
+use prompt::testing::Script;
+use prompt::{Completer, Input, PathCompleter, WordCompleter};
+use std::fs;
+use termion::event::Key;
+
+fn fruits() -> WordCompleter {
+    WordCompleter::new(vec!["apple", "apricot", "banana"])
+}
+
+#[test]
+fn tab_cycles_candidates() {
+    let script = Script::new()
+        .text("ap")
+        .keys([Key::Char('\t'), Key::Char('\t')]);
+    let (mut form, term) = session(script);
+    form.run(Input::new("Fruit").completer(fruits()))
+        .unwrap_err();
+
+    let screen = term.screen();
+    assert_eq!(screen[0], "? Fruit apricot");
+    assert!(screen[2].ends_with("apple"));
+    assert!(screen[3].ends_with("apricot"));
+}
+
+#[test]
+fn enter_takes_candidate_and_clears_list() {
+    let script = Script::new()
+        .text("ap")
+        .keys([Key::Char('\t'), Key::Char('\t')])
+        .enter()
+        .enter();
+    let (mut form, term) = session(script);
+    let fruit = form.run(Input::new("Fruit").completer(fruits())).unwrap();
+
+    assert_eq!(fruit, "apricot");
+    assert_eq!(term.contents(), "? Fruit apricot");
+}
+
+#[test]
+fn esc_restores_input() {
+    let script = Script::new()
+        .text("ap")
+        .keys([Key::Char('\t'), Key::Esc])
+        .text("e")
+        .enter();
+    let (mut form, _) = session(script);
+    let fruit = form.run(Input::new("Fruit").completer(fruits())).unwrap();
+
+    assert_eq!(fruit, "ape");
+}
+
+#[test]
+fn completes_choices() {
+    let choices = ["Pizza", "Pasta", "Burger"];
+    let script = Script::new().text("B").key(Key::Char('\t')).enter();
+    let (mut form, _) = session(script);
+    let food = form
+        .run(Input::new("Food").completer(&choices[..]))
+        .unwrap();
+
+    assert_eq!(food, "Burger");
+    assert_eq!(choices[..].complete("P"), vec!["Pizza", "Pasta"]);
+}
+
+#[test]
+fn completes_paths() {
+    let dir = std::env::temp_dir().join(format!("prompt-complete-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("alps")).unwrap();
+    fs::write(dir.join("alpha.txt"), "").unwrap();
+    fs::write(dir.join(".alpine"), "").unwrap();
+
+    let base = format!("{}/", dir.display());
+    let completer = PathCompleter::new();
+    assert_eq!(
+        completer.complete(&format!("{}al", base)),
+        vec![format!("{}alpha.txt", base), format!("{}alps/", base)]
+    );
+    assert_eq!(
+        completer.complete(&format!("{}.al", base)),
+        vec![format!("{}.alpine", base)]
+    );
+    assert_eq!(
+        PathCompleter::new().dirs_only(true).complete(&base),
+        vec![format!("{}alps/", base)]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
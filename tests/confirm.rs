@@ -0,0 +1,42 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::Confirm;
+use termion::event::Key;
+
+#[test]
+fn enter_picks_default() {
+    let (mut form, term) = session(Script::new().enter());
+    assert!(form.run(Confirm::new("Continue?")).unwrap());
+    assert_eq!(term.screen()[0], "? Continue? yes");
+
+    let (mut form, _) = session(Script::new().enter());
+    assert!(!form.run(Confirm::new("Continue?").default(false)).unwrap());
+}
+
+#[test]
+fn answers_with_y_and_n() {
+    let (mut form, term) = session(Script::new().text("xn"));
+    assert!(!form.run(Confirm::new("Continue?")).unwrap());
+    assert_eq!(term.screen()[0], "? Continue? no");
+
+    let (mut form, _) = session(Script::new().text("y"));
+    assert!(form.run(Confirm::new("Continue?").default(false)).unwrap());
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(Confirm::new("Continue?")).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(!term.is_raw());
+}
+
+#[test]
+fn runs_out_of_input() {
+    let (mut form, _) = session(Script::new().text("x"));
+    let err = form.run(Confirm::new("Continue?")).unwrap_err();
+    assert!(matches!(err, Error::NoMoreInput));
+}
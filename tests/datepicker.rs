@@ -0,0 +1,205 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Date, DatePicker, DateTime, Form, Theme, Time};
+use termion::event::Key;
+
+fn date(year: i32, month: u8, day: u8) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+#[test]
+fn enter_picks_default() {
+    let (mut form, term) = session(Script::new().enter());
+    let picked = form
+        .run(DatePicker::new("Date").default(date(2024, 3, 15)))
+        .unwrap();
+    assert_eq!(picked, date(2024, 3, 15));
+    assert_eq!(term.contents(), "? Date 2024-03-15");
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn draws_the_month() {
+    let (mut form, term) = session(Script::new());
+    form.run(DatePicker::new("Date").default(date(2024, 3, 15)))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[0], "? Date 2024-03-15");
+    assert_eq!(screen[1].trim(), "March 2024");
+    assert_eq!(screen[2], "  Mo Tu We Th Fr Sa Su");
+    assert_eq!(screen[3], "               1  2  3");
+    assert_eq!(screen[5], "  11 12 13 14 15 16 17");
+    assert_eq!(screen[7], "  25 26 27 28 29 30 31");
+    assert_eq!(screen[8], "");
+}
+
+#[test]
+fn arrows_move_days_and_weeks() {
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Down, Key::Down, Key::Left])
+        .key(Key::PageDown)
+        .enter();
+    let (mut form, term) = session(script);
+    let picked = form
+        .run(DatePicker::new("Date").default(date(2024, 3, 15)))
+        .unwrap();
+    assert_eq!(picked, date(2024, 4, 29));
+    assert_eq!(term.contents(), "? Date 2024-04-29");
+
+    let script = Script::new().key(Key::Up).key(Key::Up).enter();
+    let (mut form, _) = session(script);
+    let picked = form
+        .run(DatePicker::new("Date").default(date(2024, 1, 3)))
+        .unwrap();
+    assert_eq!(picked, date(2023, 12, 20));
+}
+
+#[test]
+fn page_keeps_day_within_month() {
+    let script = Script::new().key(Key::PageDown).enter();
+    let (mut form, _) = session(script);
+    let picked = form
+        .run(DatePicker::new("Date").default(date(2024, 1, 31)))
+        .unwrap();
+    assert_eq!(picked, date(2024, 2, 29));
+
+    let script = Script::new().key(Key::PageUp).enter();
+    let (mut form, _) = session(script);
+    let picked = form
+        .run(DatePicker::new("Date").default(date(2023, 3, 31)))
+        .unwrap();
+    assert_eq!(picked, date(2023, 2, 28));
+}
+
+#[test]
+fn stays_within_bounds() {
+    let picker = || {
+        DatePicker::new("Date")
+            .default(date(2024, 3, 15))
+            .min(date(2024, 3, 10))
+            .max(date(2024, 3, 20))
+    };
+
+    let script = Script::new().keys(vec![Key::Up; 3]).enter();
+    let (mut form, _) = session(script);
+    assert_eq!(form.run(picker()).unwrap(), date(2024, 3, 10));
+
+    let script = Script::new().key(Key::PageDown).enter();
+    let (mut form, _) = session(script);
+    assert_eq!(form.run(picker()).unwrap(), date(2024, 3, 20));
+
+    let (mut form, _) = session(Script::new().enter());
+    let picked = form
+        .run(
+            DatePicker::new("Date")
+                .default(date(2024, 1, 1))
+                .min(date(2024, 3, 10)),
+        )
+        .unwrap();
+    assert_eq!(picked, date(2024, 3, 10));
+}
+
+#[test]
+fn picks_a_time() {
+    let script = Script::new()
+        .key(Key::Right)
+        .key(Key::Char('\t'))
+        .keys(vec![Key::Up; 2])
+        .key(Key::Right)
+        .key(Key::Down)
+        .enter();
+    let (mut form, term) = session(script);
+    let picked = form
+        .run(
+            DatePicker::new("Date")
+                .default(date(2024, 3, 15))
+                .with_time()
+                .minute_step(15),
+        )
+        .unwrap();
+    assert_eq!(
+        picked,
+        DateTime::new(date(2024, 3, 16), Time::new(2, 45).unwrap())
+    );
+    assert_eq!(term.contents(), "? Date 2024-03-16 02:45");
+
+    let (mut form, term) = session(Script::new());
+    form.run(
+        DatePicker::new("Date")
+            .default(date(2024, 3, 15))
+            .with_time(),
+    )
+    .unwrap_err();
+    assert_eq!(term.screen()[9], "  Time 00:00");
+}
+
+#[test]
+fn starts_at_the_given_today() {
+    let (mut form, _) = session(Script::new().enter());
+    let picked = form
+        .run(DatePicker::new("Date").today(date(2024, 3, 15)))
+        .unwrap();
+    assert_eq!(picked, date(2024, 3, 15));
+
+    let (mut form, _) = session(Script::new().key(Key::Char('t')).enter());
+    let picked = form
+        .run(
+            DatePicker::new("Date")
+                .default(date(2020, 1, 1))
+                .today(date(2024, 3, 15)),
+        )
+        .unwrap();
+    assert_eq!(picked, date(2024, 3, 15));
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(DatePicker::new("Date")).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn parses_answers_when_not_interactive() {
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        let result = Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(DatePicker::new("Date").min(date(2024, 1, 1)).with_time());
+        (result, String::from_utf8(output).unwrap())
+    };
+
+    let (picked, output) = run("2024-03-15 09:30\n");
+    assert_eq!(
+        picked.unwrap(),
+        DateTime::new(date(2024, 3, 15), Time::new(9, 30).unwrap())
+    );
+    assert_eq!(output, "? Date 2024-03-15 09:30\n");
+
+    assert!(matches!(run("").0, Err(Error::NoAnswer(_))));
+    assert!(matches!(
+        run("2024-02-30 09:30\n").0,
+        Err(Error::InvalidAnswer(..))
+    ));
+    let (err, _) = run("2023-12-31 23:59\n");
+    assert!(
+        matches!(err, Err(Error::InvalidAnswer(_, msg)) if msg == "Must be 2024-01-01 00:00 or later")
+    );
+}
+
+#[test]
+fn date_arithmetic() {
+    assert_eq!(date(2024, 3, 15).weekday(), 4);
+    assert_eq!(date(1970, 1, 1).weekday(), 3);
+    assert_eq!(date(2024, 2, 28).add_days(2), date(2024, 3, 1));
+    assert_eq!(date(2000, 12, 31).add_days(1), date(2001, 1, 1));
+    assert_eq!(date(1900, 3, 1).add_days(-1), date(1900, 2, 28));
+    assert_eq!(date(2024, 12, 15).add_months(1), date(2025, 1, 15));
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!("2024-03-05".parse::<Date>().unwrap(), date(2024, 3, 5));
+    assert_eq!(date(2024, 3, 5).to_string(), "2024-03-05");
+    assert!("2024-13-01".parse::<Date>().is_err());
+}
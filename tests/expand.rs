@@ -0,0 +1,107 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Expand, Form, Keyed, Theme};
+use termion::event::Key;
+
+fn choices() -> Vec<Keyed<&'static str>> {
+    vec![
+        Keyed::new('y', "Overwrite"),
+        Keyed::new('n', "Skip"),
+        Keyed::new('a', "Overwrite all"),
+        Keyed::new('d', "Show diff"),
+    ]
+}
+
+#[test]
+fn key_picks_at_once() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().text("xa"));
+    let choice = form.run(Expand::new("Conflict", &choices)).unwrap();
+    assert_eq!(*choice, "Overwrite all");
+    assert_eq!(term.contents(), "? Conflict Overwrite all");
+
+    let (mut form, _) = session(Script::new().text("D"));
+    let choice = form.run(Expand::new("Conflict", &choices)).unwrap();
+    assert_eq!(*choice, "Show diff");
+}
+
+#[test]
+fn enter_picks_default() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new());
+    form.run(Expand::new("Conflict", &choices).default('n'))
+        .unwrap_err();
+    assert_eq!(term.screen()[0], "? Conflict [yNadh]");
+
+    let (mut form, _) = session(Script::new().enter());
+    let choice = form
+        .run(Expand::new("Conflict", &choices).default('n'))
+        .unwrap();
+    assert_eq!(*choice, "Skip");
+
+    // Without a default Enter does nothing
+    let (mut form, _) = session(Script::new().enter().text("y"));
+    let choice = form.run(Expand::new("Conflict", &choices)).unwrap();
+    assert_eq!(*choice, "Overwrite");
+}
+
+#[test]
+fn help_expands_the_list() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().text("h"));
+    form.run(Expand::new("Conflict", &choices)).unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "y) Overwrite");
+    assert_eq!(screen[4].trim(), "d) Show diff");
+    assert_eq!(screen[5].trim(), "h) Help, list all options");
+
+    let (mut form, term) = session(Script::new().text("hd"));
+    form.run(Expand::new("Conflict", &choices)).unwrap();
+    assert_eq!(term.contents(), "? Conflict Show diff");
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(Expand::new("Conflict", &choices)).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(!term.is_raw());
+}
+
+#[test]
+fn keys_must_be_unique() {
+    let choices = vec![Keyed::new('y', "Yes"), Keyed::new('Y', "Yup")];
+    let (mut form, _) = session(Script::new().text("y"));
+    let err = form.run(Expand::new("Conflict", &choices)).unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(_, msg) if msg == "duplicate key `y`"));
+
+    let choices = vec![Keyed::new('y', "Yes"), Keyed::new('h', "Hold")];
+    let mut output = Vec::new();
+    let err = Form::new("y\n".as_bytes(), &mut output, Theme::new())
+        .run(Expand::new("Conflict", &choices))
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(_, msg) if msg == "`h` is reserved for help"));
+}
+
+#[test]
+fn takes_key_or_text_when_not_interactive() {
+    let choices = choices();
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        let result = Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(Expand::new("Conflict", &choices).default('n'))
+            .copied();
+        (result, String::from_utf8(output).unwrap())
+    };
+
+    let (choice, output) = run("a\n");
+    assert_eq!(choice.unwrap(), "Overwrite all");
+    assert_eq!(output, "? Conflict Overwrite all\n");
+    assert_eq!(run("Show diff\n").0.unwrap(), "Show diff");
+    assert_eq!(run("\n").0.unwrap(), "Skip");
+    assert!(matches!(run("q\n").0, Err(Error::InvalidAnswer(..))));
+}
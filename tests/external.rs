@@ -0,0 +1,95 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::ExternalEditor;
+use std::fs;
+use std::path::PathBuf;
+use termion::event::Key;
+
+/// A shell script acting as the editor, run as `sh <script> <file>`
+fn editor(name: &str, script: &str) -> (PathBuf, String) {
+    let path =
+        std::env::temp_dir().join(format!("prompt-editor-{}-{}.sh", std::process::id(), name));
+    fs::write(&path, script).unwrap();
+    let command = format!("sh {}", path.display());
+    (path, command)
+}
+
+#[test]
+fn edits_template() {
+    let (script, command) = editor("append", "printf 'body\\n' >> \"$1\"\n");
+    let (mut form, term) = session(Script::new().enter());
+    let text = form
+        .run(
+            ExternalEditor::new("Message")
+                .template("subject\n")
+                .command(&command),
+        )
+        .unwrap();
+
+    assert_eq!(text, "subject\nbody");
+    assert_eq!(term.contents(), "? Message (2 lines)");
+    fs::remove_file(script).unwrap();
+}
+
+#[test]
+fn re_edits_until_valid() {
+    let (script, command) = editor("grow", "printf 'x\\n' >> \"$1\"\n");
+    let (mut form, term) = session(Script::new().enter());
+    let err = form
+        .run(
+            ExternalEditor::new("Message")
+                .command(&command)
+                .validate(valid::MinLen(3)),
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::NoMoreInput));
+    assert!(term.screen()[1].starts_with("! "));
+
+    let (mut form, term) = session(Script::new().enter().enter());
+    let text = form
+        .run(
+            ExternalEditor::new("Message")
+                .command(&command)
+                .validate(valid::MinLen(3)),
+        )
+        .unwrap();
+    assert_eq!(text, "x\nx");
+    assert_eq!(term.contents(), "? Message (2 lines)");
+    fs::remove_file(script).unwrap();
+}
+
+#[test]
+fn editor_failure_is_an_error() {
+    let (script, command) = editor("fail", "exit 3\n");
+    let (mut form, _) = session(Script::new().enter());
+    let err = form
+        .run(ExternalEditor::new("Message").command(&command))
+        .unwrap_err();
+
+    assert!(matches!(err, Error::IoError(_)));
+    fs::remove_file(script).unwrap();
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, _) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form
+        .run(ExternalEditor::new("Message").command("false"))
+        .unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+}
+
+#[test]
+fn command_is_split_by_the_shell() {
+    let (script, command) = editor("quoted", "printf '%s\\n' \"$1\" >> \"$2\"\n");
+    let (mut form, _) = session(Script::new().enter());
+    let text = form
+        .run(ExternalEditor::new("Message").command(&format!("{} 'two words'", command)))
+        .unwrap();
+
+    assert_eq!(text, "two words");
+    fs::remove_file(script).unwrap();
+}
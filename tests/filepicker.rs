@@ -0,0 +1,213 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{FilePicker, Form, Theme};
+use std::fs;
+use std::path::PathBuf;
+use termion::event::Key;
+
+/// A small project layout, removed when dropped
+struct Project(PathBuf);
+
+impl Project {
+    fn new(name: &str) -> Project {
+        let root = std::env::temp_dir().join(format!("pf-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        for dir in &["docs", "src", ".git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in &[
+            "docs/guide.md",
+            "src/main.rs",
+            "Cargo.toml",
+            "README.md",
+            ".env",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        Project(fs::canonicalize(root).unwrap())
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn rows(screen: &[String], count: usize) -> Vec<String> {
+    screen[1..=count]
+        .iter()
+        .map(|r| r.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn lists_directories_first() {
+    let project = Project::new("list");
+    let (mut form, term) = session(Script::new());
+    form.run(FilePicker::new("File").start(&project.0))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[0], format!("? File {}/", project.0.display()));
+    assert_eq!(
+        rows(&screen, 5),
+        vec!["❯ docs/", "src/", "Cargo.toml", "README.md", ""]
+    );
+
+    let (mut form, term) = session(Script::new());
+    form.run(FilePicker::new("File").start(&project.0).hidden(true))
+        .unwrap_err();
+    assert_eq!(
+        rows(&term.screen(), 6),
+        vec![
+            "❯ .git/",
+            "docs/",
+            "src/",
+            ".env",
+            "Cargo.toml",
+            "README.md"
+        ]
+    );
+}
+
+#[test]
+fn enter_opens_directories_and_picks_files() {
+    let project = Project::new("enter");
+    let (mut form, term) = session(Script::new().enter().enter());
+    let path = form.run(FilePicker::new("File").start(&project.0)).unwrap();
+    assert_eq!(path, project.path("docs/guide.md"));
+    assert_eq!(
+        term.contents(),
+        format!("? File {}", project.path("docs/guide.md").display())
+    );
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn backspace_goes_up() {
+    let project = Project::new("up");
+    let script = Script::new().key(Key::Backspace);
+    let (mut form, term) = session(script);
+    form.run(FilePicker::new("File").start(project.path("src")))
+        .unwrap_err();
+    assert_eq!(term.screen()[2].trim(), "❯ src/");
+
+    let script = Script::new().key(Key::Backspace).key(Key::Down).enter();
+    let (mut form, _) = session(script);
+    let path = form
+        .run(FilePicker::new("File").start(project.path("src")))
+        .unwrap();
+    assert_eq!(path, project.path("Cargo.toml"));
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let project = Project::new("scroll");
+    let script = Script::new().keys(vec![Key::Down; 5]);
+    let (mut form, term) = session(script);
+    form.run(FilePicker::new("File").start(&project.0).page_size(2))
+        .unwrap_err();
+    assert_eq!(
+        rows(&term.screen(), 3),
+        vec!["Cargo.toml", "❯ README.md", ""]
+    );
+}
+
+#[test]
+fn typing_filters() {
+    let project = Project::new("filter");
+    let script = Script::new().text("rdm");
+    let (mut form, term) = session(script);
+    form.run(FilePicker::new("File").start(&project.0))
+        .unwrap_err();
+    assert!(term.screen()[0].ends_with("/rdm"));
+    assert_eq!(rows(&term.screen(), 2), vec!["❯ README.md", ""]);
+
+    let script = Script::new().text("rdx").key(Key::Backspace).enter();
+    let (mut form, _) = session(script);
+    let path = form.run(FilePicker::new("File").start(&project.0)).unwrap();
+    assert_eq!(path, project.path("README.md"));
+}
+
+#[test]
+fn filters_by_extension() {
+    let project = Project::new("ext");
+    let (mut form, term) = session(Script::new());
+    form.run(
+        FilePicker::new("File")
+            .start(&project.0)
+            .extensions(&["MD"]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        rows(&term.screen(), 4),
+        vec!["❯ docs/", "src/", "README.md", ""]
+    );
+}
+
+#[test]
+fn picks_directories() {
+    let project = Project::new("dirs");
+    let (mut form, term) = session(Script::new());
+    form.run(FilePicker::new("Dir").start(&project.0).dirs_only(true))
+        .unwrap_err();
+    assert_eq!(rows(&term.screen(), 4), vec!["❯ ./", "docs/", "src/", ""]);
+
+    let script = Script::new().key(Key::Down).enter().enter();
+    let (mut form, _) = session(script);
+    let path = form
+        .run(FilePicker::new("Dir").start(&project.0).dirs_only(true))
+        .unwrap();
+    assert_eq!(path, project.path("docs"));
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let project = Project::new("abort");
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form
+        .run(FilePicker::new("File").start(&project.0))
+        .unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+    assert_eq!(term.screen()[1], "");
+}
+
+#[test]
+fn takes_paths_when_not_interactive() {
+    let project = Project::new("answer");
+    let run = |input: &str, dirs_only: bool| {
+        let mut output = Vec::new();
+        Form::new(input.as_bytes(), &mut output, Theme::new()).run(
+            FilePicker::new("File")
+                .start(&project.0)
+                .dirs_only(dirs_only),
+        )
+    };
+
+    assert_eq!(
+        run("docs/guide.md\n", false).unwrap(),
+        project.path("docs/guide.md")
+    );
+    assert_eq!(run("docs\n", true).unwrap(), project.path("docs"));
+    assert!(matches!(
+        run("docs\n", false),
+        Err(Error::InvalidAnswer(..))
+    ));
+    assert!(matches!(
+        run("Cargo.toml\n", true),
+        Err(Error::InvalidAnswer(..))
+    ));
+    assert!(matches!(
+        run("missing\n", false),
+        Err(Error::InvalidAnswer(..))
+    ));
+    assert!(matches!(run("", false), Err(Error::NoAnswer(..))));
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::session;
+use prompt::testing::Script;
+use prompt::{History, Input};
+use std::fs;
+use termion::event::Key;
+
+fn history(entries: &[&str]) -> History {
+    let history = History::new();
+    for entry in entries {
+        history.add(entry).unwrap();
+    }
+    history
+}
+
+#[test]
+fn recalls_with_up_and_down() {
+    let history = history(&["alpha", "beta", "gamma"]);
+    let script = Script::new()
+        .keys([Key::Up, Key::Up, Key::Up, Key::Up, Key::Down])
+        .enter();
+    let (mut form, _) = session(script);
+    let answer = form.run(Input::new("Host").history(&history)).unwrap();
+
+    assert_eq!(answer, "beta");
+    assert_eq!(history.entries(), vec!["alpha", "gamma", "beta"]);
+}
+
+#[test]
+fn down_restores_typed_text() {
+    let history = history(&["alpha"]);
+    let script = Script::new()
+        .text("typ")
+        .keys([Key::Up, Key::Down])
+        .text("ed")
+        .enter();
+    let (mut form, _) = session(script);
+    let answer = form.run(Input::new("Host").history(&history)).unwrap();
+
+    assert_eq!(answer, "typed");
+}
+
+#[test]
+fn reverse_search() {
+    let history = history(&["web1", "db", "web2"]);
+    let script = Script::new()
+        .key(Key::Ctrl('r'))
+        .text("web")
+        .key(Key::Ctrl('r'));
+    let (mut form, term) = session(script);
+    form.run(Input::new("Host").history(&history)).unwrap_err();
+    assert_eq!(term.screen()[0], "? Host (reverse-i-search)`web': web1");
+
+    let script = Script::new()
+        .key(Key::Ctrl('r'))
+        .text("db")
+        .key(Key::End)
+        .text("2")
+        .enter();
+    let (mut form, _) = session(script);
+    let answer = form.run(Input::new("Host").history(&history)).unwrap();
+    assert_eq!(answer, "db2");
+}
+
+#[test]
+fn failed_search() {
+    let history = history(&["alpha"]);
+    let script = Script::new().key(Key::Ctrl('r')).text("x");
+    let (mut form, term) = session(script);
+    form.run(Input::new("Host").history(&history)).unwrap_err();
+    assert_eq!(term.screen()[0], "? Host (failed reverse-i-search)`x':");
+}
+
+#[test]
+fn file_backed_with_limit() {
+    let path = std::env::temp_dir().join(format!("prompt-history-{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let history = History::open(&path).unwrap().limit(2);
+    for entry in &["one", "two", "one", "three", ""] {
+        history.add(entry).unwrap();
+    }
+    assert_eq!(history.entries(), vec!["one", "three"]);
+
+    let reopened = History::open(&path).unwrap();
+    assert_eq!(reopened.entries(), vec!["one", "three"]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn answers_when_saving_fails() {
+    let path = std::env::temp_dir()
+        .join(format!("prompt-missing-{}", std::process::id()))
+        .join("history");
+    let history = History::open(&path).unwrap();
+
+    let (mut form, _) = session(Script::new().text("ls").enter());
+    let line = form.run(Input::new("$").history(&history)).unwrap();
+    assert_eq!(line, "ls");
+    assert_eq!(history.entries(), vec!["ls"]);
+}
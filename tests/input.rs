@@ -0,0 +1,194 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::Input;
+use termion::event::Key;
+
+#[test]
+fn returns_typed_text() {
+    let (mut form, term) = session(Script::new().text("Rasmus").enter());
+    let name = form.run(Input::new("Name")).unwrap();
+    assert_eq!(name, "Rasmus");
+    assert_eq!(term.screen()[0], "? Name Rasmus");
+}
+
+#[test]
+fn uses_default_on_empty_input() {
+    let (mut form, term) = session(Script::new().enter());
+    let name = form.run(Input::new("Name").default("Rasmus")).unwrap();
+    assert_eq!(name, "Rasmus");
+    assert_eq!(term.screen()[0], "? Name Rasmus");
+}
+
+#[test]
+fn retries_until_valid() {
+    let (mut form, term) = session(Script::new().text("abc").enter().text("abcdef").enter());
+    let name = form
+        .run(Input::new("Name").validate(valid::MinLen(5)))
+        .unwrap();
+    assert_eq!(name, "abcdef");
+    assert_eq!(term.contents(), "? Name abcdef");
+}
+
+#[test]
+fn shows_validation_error() {
+    let (mut form, term) = session(Script::new().enter());
+    let err = form.run(Input::new("Name").required()).unwrap_err();
+    assert!(matches!(err, Error::NoMoreInput));
+    assert!(term.screen()[1].starts_with("! "));
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, _) = session(Script::new().text("Ras").key(Key::Ctrl('c')));
+    let err = form.run(Input::new("Name")).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+}
+
+#[test]
+fn moves_cursor() {
+    let script = Script::new()
+        .text("Rsmus")
+        .keys([Key::Home, Key::Right])
+        .text("a")
+        .keys([
+            Key::End,
+            Key::Left,
+            Key::Delete,
+            Key::Ctrl('a'),
+            Key::Delete,
+        ])
+        .text("K")
+        .keys([Key::Ctrl('e')])
+        .text("s")
+        .enter();
+    let (mut form, _) = session(script);
+    let name = form.run(Input::new("Name")).unwrap();
+    assert_eq!(name, "Kasmus");
+}
+
+#[test]
+fn deletes_words_and_lines() {
+    let script = Script::new()
+        .text("foo bar  baz")
+        .key(Key::Ctrl('w'))
+        .key(Key::Ctrl('w'))
+        .text("qux")
+        .keys([Key::Left, Key::Left, Key::Ctrl('u')])
+        .enter();
+    let (mut form, _) = session(script);
+    let name = form.run(Input::new("Name")).unwrap();
+    assert_eq!(name, "ux");
+}
+
+#[test]
+fn edits_graphemes() {
+    let script = Script::new()
+        .text("e\u{301}日本")
+        .keys([Key::Left, Key::Left, Key::Backspace])
+        .text("x")
+        .enter();
+    let (mut form, _) = session(script);
+    let name = form.run(Input::new("Name")).unwrap();
+    assert_eq!(name, "x日本");
+}
+
+#[test]
+fn places_cursor_by_display_width() {
+    let script = Script::new().text("日本語").key(Key::Left);
+    let (mut form, term) = session(script);
+    form.run(Input::new("Name")).unwrap_err();
+    assert_eq!(term.cursor(), (11, 0));
+}
+
+#[test]
+fn parses_with_from_str() {
+    let script = Script::new()
+        .text("10.0.0")
+        .enter()
+        .text("10.0.0.1")
+        .enter();
+    let (mut form, term) = session(script);
+    let ip = form
+        .run(Input::new("Address").parse::<std::net::IpAddr>())
+        .unwrap();
+
+    assert_eq!(ip, "10.0.0.1".parse::<std::net::IpAddr>().unwrap());
+    assert_eq!(term.contents(), "? Address 10.0.0.1");
+}
+
+#[test]
+fn parses_with_closure() {
+    let parse_secs = |input: &str| {
+        input
+            .trim_end_matches('s')
+            .parse::<u64>()
+            .map(std::time::Duration::from_secs)
+            .map_err(|_| "expected seconds, like 30s")
+    };
+    let (mut form, term) = session(Script::new().text("soon").enter());
+    let err = form
+        .run(Input::new("Timeout").parser(parse_secs))
+        .unwrap_err();
+    assert!(matches!(err, Error::NoMoreInput));
+    assert_eq!(term.screen()[1], "! expected seconds, like 30s");
+
+    let (mut form, _) = session(Script::new().enter());
+    let timeout = form
+        .run(Input::new("Timeout").default("30s").parser(parse_secs))
+        .unwrap();
+    assert_eq!(timeout, std::time::Duration::from_secs(30));
+}
+
+#[test]
+fn validates_before_parsing() {
+    let mut out = Vec::new();
+    let mut form = prompt::Form::new(&b"12\n"[..], &mut out, prompt::Theme::new());
+    let err = form
+        .run(Input::new("Port").validate(valid::MinLen(3)).parse::<u16>())
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(_, _)));
+}
+
+#[test]
+fn wraps_long_input() {
+    // "? Name " takes 7 of the 40 columns
+    let text = "a".repeat(33) + "bcdef";
+    let script = Script::new().text(&text).keys([Key::Left, Key::Left]);
+    let (mut form, term) = session(script);
+    form.run(Input::new("Name").validate(valid::MinLen(50)))
+        .unwrap_err();
+    assert_eq!(term.screen()[0], format!("? Name {}", "a".repeat(33)));
+    assert_eq!(term.screen()[1], "bcdef");
+    assert_eq!(term.cursor(), (3, 1));
+
+    let script = Script::new()
+        .text(&text)
+        .key(Key::Home)
+        .text("x")
+        .enter()
+        .key(Key::Backspace);
+    let (mut form, term) = session(script);
+    form.run(Input::new("Name").validate(valid::MinLen(50)))
+        .unwrap_err();
+    // The wrapped row is cleared along with the line
+    assert_eq!(term.screen()[0], "? Name");
+    assert!(term.screen()[1].starts_with("! "));
+    assert_eq!(term.screen()[2], "");
+    assert_eq!(term.cursor(), (7, 0));
+}
+
+#[test]
+fn moves_to_a_new_row_at_the_edge() {
+    let text = "a".repeat(33);
+    let (mut form, term) = session(Script::new().text(&text).enter());
+    let name = form.run(Input::new("Name")).unwrap();
+    assert_eq!(name, text);
+    assert_eq!(term.contents(), format!("? Name {}", text));
+
+    let (mut form, term) = session(Script::new().text(&text));
+    form.run(Input::new("Name")).unwrap_err();
+    assert_eq!(term.cursor(), (0, 1));
+}
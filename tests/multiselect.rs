@@ -0,0 +1,219 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Entry, Form, MultiSelect, Selection, Theme};
+use termion::event::Key;
+
+fn choices() -> Vec<String> {
+    (1..=20).map(|i| format!("Choice {}", i)).collect()
+}
+
+#[test]
+fn toggles_with_space() {
+    let choices = choices();
+    let script = Script::new()
+        .text(" ")
+        .key(Key::Down)
+        .key(Key::Down)
+        .text(" ")
+        .key(Key::Down)
+        .text("  ")
+        .enter();
+    let (mut form, term) = session(script);
+    let picked = form
+        .run(MultiSelect::new("Pick", &choices).build())
+        .unwrap();
+    assert_eq!(picked, vec!["Choice 1", "Choice 3"]);
+    assert_eq!(term.contents(), "? Pick Choice 1, Choice 3");
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let choices = choices();
+    let script = Script::new()
+        .keys(vec![Key::Down; 10])
+        .text(" ")
+        .keys(vec![Key::Up; 9])
+        .text(" ");
+    let (mut form, term) = session(script);
+    form.run(MultiSelect::new("Pick", &choices).page_size(4).build())
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "❯ ◉ Choice 2");
+    assert_eq!(screen[4].trim(), "◯ Choice 5");
+}
+
+#[test]
+fn retries_until_valid() {
+    let choices = choices();
+    let script = Script::new()
+        .enter()
+        .text(" ")
+        .key(Key::Down)
+        .text(" ")
+        .enter();
+    let (mut form, term) = session(script);
+    let picked = form
+        .run(
+            MultiSelect::new("Pick", &choices)
+                .validate(valid::MinLen(2))
+                .build(),
+        )
+        .unwrap();
+    assert_eq!(picked, vec!["Choice 1", "Choice 2"]);
+    assert_eq!(term.contents(), "? Pick Choice 1, Choice 2");
+}
+
+#[test]
+fn shows_validation_error() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().enter());
+    form.run(
+        MultiSelect::new("Pick", &choices)
+            .page_size(3)
+            .validate(valid::MinLen(1))
+            .build(),
+    )
+    .unwrap_err();
+    assert!(term.screen()[4].starts_with("! "));
+}
+
+#[test]
+fn empty_choices_are_no_answer() {
+    let choices: Vec<String> = Vec::new();
+    let (mut form, term) = session(Script::new().enter());
+    let err = form
+        .run(MultiSelect::new("Pick", &choices).build())
+        .unwrap_err();
+    assert!(matches!(err, Error::NoAnswer(msg) if msg == "Pick"));
+    assert!(term.screen().iter().all(|line| line.is_empty()));
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().text(" ").key(Key::Ctrl('c')));
+    let err = form
+        .run(MultiSelect::new("Pick", &choices).build())
+        .unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+    assert!(!term.is_raw());
+}
+
+#[test]
+fn owns_choices_from_an_iterator() {
+    let script = Script::new()
+        .enter()
+        .text(" ")
+        .key(Key::Down)
+        .key(Key::Down)
+        .text(" ")
+        .enter();
+    let (mut form, term) = session(script);
+    let picked: Vec<(String, usize)> = form
+        .run(
+            MultiSelect::new(
+                "Pick",
+                (1..=5)
+                    .map(|i| (format!("Choice {}", i), i))
+                    .collect::<Vec<_>>(),
+            )
+            .validate(valid::MinLen(2)),
+        )
+        .unwrap();
+    assert_eq!(
+        picked,
+        vec![("Choice 1".to_string(), 1), ("Choice 3".to_string(), 3)]
+    );
+    assert_eq!(term.contents(), "? Pick Choice 1, Choice 3");
+}
+
+#[test]
+fn starts_with_preselected() {
+    let choices = choices();
+    let script = Script::new().key(Key::Down).text(" ").enter();
+    let (mut form, _) = session(script);
+    let picked = form
+        .run(MultiSelect::new("Pick", &choices).selected(vec![1, 4, 40]))
+        .unwrap();
+    assert_eq!(picked, vec!["Choice 2", "Choice 3", "Choice 5"]);
+
+    let (mut form, term) = session(Script::new());
+    form.run(MultiSelect::new("Pick", &choices).selected_by(|c| c.ends_with('3')))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "◯ Choice 1");
+    assert_eq!(screen[3].trim(), "❯ ◉ Choice 3");
+}
+
+#[test]
+fn preselected_answers_when_not_interactive() {
+    let choices = choices();
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        Form::new(input.as_bytes(), &mut output, Theme::new()).run(
+            MultiSelect::new("Pick", &choices)
+                .selected(vec![2, 0])
+                .validate(valid::MaxLen(2)),
+        )
+    };
+    assert_eq!(run("").unwrap(), vec!["Choice 1", "Choice 3"]);
+    assert_eq!(run("\n").unwrap(), vec!["Choice 1", "Choice 3"]);
+    assert_eq!(run("Choice 2\n").unwrap(), vec!["Choice 2"]);
+}
+
+#[test]
+fn indexed_returns_indices_and_values() {
+    let sizes = vec![("Small", 's'), ("Medium", 'm'), ("Large", 'l')];
+    let selection = |index, value| Selection { index, value };
+
+    let script = Script::new()
+        .text(" ")
+        .key(Key::Down)
+        .key(Key::Down)
+        .text(" ");
+    let (mut form, term) = session(script.enter());
+    let picked = form
+        .run(MultiSelect::new("Sizes", &sizes).indexed())
+        .unwrap();
+    assert_eq!(picked, vec![selection(0, 's'), selection(2, 'l')]);
+    assert_eq!(term.contents(), "? Sizes Small, Large");
+
+    let mut output = Vec::new();
+    let picked = Form::new("Medium\n".as_bytes(), &mut output, Theme::new())
+        .run(
+            MultiSelect::new("Sizes", &sizes)
+                .validate(valid::MaxLen(1))
+                .indexed(),
+        )
+        .unwrap();
+    assert_eq!(picked, vec![selection(1, 'm')]);
+}
+
+#[test]
+fn skips_disabled_and_separators() {
+    let menu = vec![
+        Entry::Separator("Toppings"),
+        Entry::Enabled("Cheese"),
+        Entry::disabled("Truffle", "ask staff"),
+        Entry::Enabled("Basil"),
+    ];
+    let script = Script::new().text(" ").key(Key::Down).text(" ");
+    let (mut form, term) = session(script);
+    form.run(MultiSelect::new("Pick", &menu).selected(vec![0, 2]))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Toppings");
+    assert_eq!(screen[2].trim(), "◉ Cheese");
+    assert_eq!(screen[3].trim(), "◯ Truffle (ask staff)");
+    assert_eq!(screen[4].trim(), "❯ ◉ Basil");
+
+    let (mut form, _) = session(Script::new().enter());
+    let picked = form
+        .run(MultiSelect::new("Pick", &menu).selected(vec![0, 2]))
+        .unwrap();
+    assert!(picked.is_empty());
+}
@@ -0,0 +1,114 @@
+use prompt::error::Error;
+use prompt::{Confirm, Form, Input, MultiSelect, Password, Plain, Select, Theme};
+
+fn run<F, T>(input: &str, f: F) -> (prompt::error::Result<T>, String)
+where
+    F: FnOnce(&mut Form<&[u8], &mut Vec<u8>>) -> prompt::error::Result<T>,
+{
+    let mut output = Vec::new();
+    let result = {
+        let mut form = Form::new(input.as_bytes(), &mut output, Theme::new());
+        assert!(!form.is_interactive());
+        f(&mut form)
+    };
+    (result, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn input_reads_a_line() {
+    let (name, output) = run("Rasmus\n", |f| f.run(Input::new("Name")));
+    assert_eq!(name.unwrap(), "Rasmus");
+    assert_eq!(output, "? Name Rasmus\n");
+
+    let (name, _) = run("", |f| f.run(Input::new("Name").default("Rasmus")));
+    assert_eq!(name.unwrap(), "Rasmus");
+
+    let (err, _) = run("", |f| f.run(Input::new("Name")));
+    assert!(matches!(err.unwrap_err(), Error::NoAnswer(msg) if msg == "Name"));
+
+    let (err, _) = run("abc\n", |f| {
+        f.run(Input::new("Name").validate(valid::MinLen(5)))
+    });
+    assert!(matches!(err.unwrap_err(), Error::InvalidAnswer(msg, _) if msg == "Name"));
+}
+
+#[test]
+fn confirm_reads_y_or_n() {
+    let (yes, output) = run("n\ny\n", |f| {
+        Ok((f.run(Confirm::new("A?"))?, f.run(Confirm::new("B?"))?))
+    });
+    assert_eq!(yes.unwrap(), (false, true));
+    assert_eq!(output, "? A? no\n? B? yes\n");
+
+    let (yes, _) = run("", |f| f.run(Confirm::new("A?").default(false)));
+    assert!(!yes.unwrap());
+
+    let (err, _) = run("maybe\n", |f| f.run(Confirm::new("A?")));
+    assert!(matches!(err.unwrap_err(), Error::InvalidAnswer(..)));
+}
+
+#[test]
+fn password_is_not_echoed() {
+    let (pass, output) = run("secret\n", |f| f.run(Password::new("Password").build()));
+    assert_eq!(pass.unwrap(), "secret");
+    assert_eq!(output, "? Password \n");
+}
+
+#[test]
+fn select_takes_index_or_text() {
+    let choices = vec!["Pizza", "Burger", "Pasta"];
+
+    let (food, output) = run("1\n", |f| f.run(Select::new("Food", &choices).build()));
+    assert_eq!(food.unwrap(), "Burger");
+    assert_eq!(output, "? Food Burger\n");
+
+    let (food, _) = run("Pasta\n", |f| f.run(Select::new("Food", &choices).build()));
+    assert_eq!(food.unwrap(), "Pasta");
+
+    let (err, _) = run("7\n", |f| f.run(Select::new("Food", &choices).build()));
+    assert!(matches!(err.unwrap_err(), Error::InvalidChoice(7)));
+
+    let (err, _) = run("", |f| f.run(Select::new("Food", &choices).build()));
+    assert!(matches!(err.unwrap_err(), Error::NoAnswer(msg) if msg == "Food"));
+}
+
+#[test]
+fn select_prefers_text_to_index() {
+    let choices = vec!["1", "2", "3"];
+
+    let (size, _) = run("2\n", |f| f.run(Select::new("Size", &choices).build()));
+    assert_eq!(size.unwrap(), "2");
+
+    let (size, _) = run("0\n", |f| f.run(Select::new("Size", &choices).build()));
+    assert_eq!(size.unwrap(), "1");
+}
+
+#[test]
+fn multi_select_takes_comma_separated_values() {
+    let choices = vec!["Pizza", "Burger", "Pasta"];
+
+    let (food, output) = run("Pasta, 0\n", |f| {
+        f.run(MultiSelect::new("Food", &choices).build())
+    });
+    assert_eq!(food.unwrap(), vec!["Pizza", "Pasta"]);
+    assert_eq!(output, "? Food Pizza, Pasta\n");
+
+    let (err, _) = run("1\n", |f| {
+        f.run(
+            MultiSelect::new("Food", &choices)
+                .validate(valid::MinLen(2))
+                .build(),
+        )
+    });
+    assert!(matches!(err.unwrap_err(), Error::InvalidAnswer(..)));
+
+    let (err, _) = run(" \n", |f| f.run(MultiSelect::new("Food", &choices).build()));
+    assert!(matches!(err.unwrap_err(), Error::NoAnswer(msg) if msg == "Food"));
+}
+
+#[test]
+fn plain_writers_are_not_interactive() {
+    let mut form = Form::new(&b"Rasmus\n"[..], Plain(std::io::sink()), Theme::new());
+    assert!(!form.is_interactive());
+    assert_eq!(form.run(Input::new("Name")).unwrap(), "Rasmus");
+}
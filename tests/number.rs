@@ -0,0 +1,97 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Int, Number};
+use termion::event::Key;
+
+#[test]
+fn parses_typed_number() {
+    let (mut form, term) = session(Script::new().text("42").enter());
+    let n: u8 = form.run(Number::new("Age")).unwrap();
+    assert_eq!(n, 42);
+    assert_eq!(term.contents(), "? Age 42");
+}
+
+#[test]
+fn shows_parse_errors_inline() {
+    let script = Script::new().text("4x").enter();
+    let (mut form, term) = session(script);
+    let err = form.run(Int::new("Count")).unwrap_err();
+
+    assert!(matches!(err, Error::NoMoreInput));
+    assert_eq!(term.screen()[1], "! \"4x\" is not a valid number");
+}
+
+#[test]
+fn checks_range() {
+    let script = Script::new()
+        .text("11")
+        .enter()
+        .key(Key::Ctrl('u'))
+        .text("10")
+        .enter();
+    let (mut form, term) = session(script);
+    let n = form.run(Int::new("Count").min(1).max(10)).unwrap();
+
+    assert_eq!(n, 10);
+    assert_eq!(term.contents(), "? Count 10");
+}
+
+#[test]
+fn steps_with_up_and_down() {
+    let script = Script::new()
+        .keys([Key::Up, Key::Up, Key::Up, Key::Down])
+        .enter();
+    let (mut form, _) = session(script);
+    let n = form
+        .run(Number::<f64>::new("Ratio").default(0.5).step(0.25).max(1.0))
+        .unwrap();
+
+    assert_eq!(n, 0.75);
+}
+
+#[test]
+fn starts_stepping_at_min() {
+    let script = Script::new().keys([Key::Down, Key::Down]).enter();
+    let (mut form, _) = session(script);
+    let n = form.run(Int::new("Count").min(3)).unwrap();
+
+    assert_eq!(n, 3);
+}
+
+#[test]
+fn uses_default_on_empty_input() {
+    let (mut form, term) = session(Script::new().enter());
+    let n = form.run(Int::new("Count").default(7)).unwrap();
+
+    assert_eq!(n, 7);
+    assert_eq!(term.contents(), "? Count 7");
+}
+
+#[test]
+fn answers_when_not_interactive() {
+    let mut out = Vec::new();
+    let mut form = prompt::Form::new(&b"300\n5\n"[..], &mut out, prompt::Theme::new());
+    let err = form.run(Number::<u8>::new("Small")).unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(_, _)));
+    assert_eq!(form.run(Int::new("Count").max(9)).unwrap(), 5);
+}
+
+#[test]
+fn rejects_nan() {
+    let script = Script::new().text("NaN").enter();
+    let (mut form, term) = session(script);
+    let err = form
+        .run(prompt::Float::new("Ratio").min(0.0).max(1.0))
+        .unwrap_err();
+
+    assert!(matches!(err, Error::NoMoreInput));
+    assert_eq!(term.screen()[1], "! \"NaN\" is not a valid number");
+
+    let mut out = Vec::new();
+    let mut form = prompt::Form::new(&b"nan\n"[..], &mut out, prompt::Theme::new());
+    let err = form.run(prompt::Float::new("Ratio")).unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(..)));
+}
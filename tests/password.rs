@@ -0,0 +1,37 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::Password;
+use termion::event::Key;
+
+#[test]
+fn reads_without_echo() {
+    let (mut form, term) = session(Script::new().text("secret").enter());
+    let pass = form.run(Password::new("Password").build()).unwrap();
+    assert_eq!(pass, "secret");
+    assert!(!term.contents().contains("secret"));
+    assert!(!term.is_raw());
+}
+
+#[test]
+fn backspace_deletes() {
+    let script = Script::new()
+        .text("secrex")
+        .key(Key::Backspace)
+        .text("t")
+        .enter();
+    let (mut form, _) = session(script);
+    assert_eq!(
+        form.run(Password::new("Password").build()).unwrap(),
+        "secret"
+    );
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, _) = session(Script::new().text("sec").key(Key::Ctrl('c')));
+    let err = form.run(Password::new("Password").build()).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::session;
+use prompt::error::{Error, Result};
+use prompt::testing::Script;
+use prompt::{
+    Answer, Answers, Confirm, FromAnswers, Input, MultiSelect, Number, Questions, Select,
+};
+use std::collections::HashMap;
+use termion::event::Key;
+
+const FOOD: &[&str] = &["Pizza", "Burger", "Pasta"];
+
+fn questions() -> Questions<'static> {
+    Questions::new()
+        .question("name", Input::new("Name"))
+        .question("hungry", Confirm::new("Hungry?"))
+        .question("food", Select::new("Food", FOOD))
+        .when(|answers| answers.get_bool("hungry") == Some(true))
+        .question("toppings", MultiSelect::new("Toppings", FOOD))
+        .when(|answers| answers.get_str("food") == Some("Pizza"))
+}
+
+#[test]
+fn asks_in_order() {
+    let script = Script::new()
+        .text("Rasmus")
+        .enter()
+        .text("y")
+        .enter()
+        .key(Key::Down)
+        .text(" ")
+        .enter();
+    let (mut form, term) = session(script);
+    let answers = form.ask(&questions()).unwrap();
+
+    assert_eq!(answers.len(), 4);
+    assert_eq!(answers.get_str("name"), Some("Rasmus"));
+    assert_eq!(answers.get_bool("hungry"), Some(true));
+    assert_eq!(answers.get_str("food"), Some("Pizza"));
+    assert_eq!(
+        answers.get("toppings"),
+        Some(&Answer::List(vec![Answer::String("Burger".to_string())]))
+    );
+    assert_eq!(
+        term.contents(),
+        "? Name Rasmus\n? Hungry? yes\n? Food Pizza\n? Toppings Burger"
+    );
+}
+
+#[test]
+fn skips_questions_by_earlier_answers() {
+    let (mut form, _) = session(Script::new().text("Rasmus").enter().text("n"));
+    let answers = form.ask(&questions()).unwrap();
+
+    assert_eq!(answers.len(), 2);
+    assert!(!answers.contains("food"));
+    assert!(!answers.contains("toppings"));
+}
+
+#[test]
+fn uses_pre_seeded_answers() {
+    let mut preset = HashMap::new();
+    preset.insert("name", "Rasmus");
+    preset.insert("food", "Pasta");
+
+    let (form, _) = session(Script::new().text("y"));
+    let mut form = form.answers(preset);
+    let answers = form.ask(&questions()).unwrap();
+
+    assert_eq!(answers.get_str("name"), Some("Rasmus"));
+    assert_eq!(answers.get_str("food"), Some("Pasta"));
+    assert!(!answers.contains("toppings"));
+}
+
+#[derive(Debug, PartialEq)]
+struct Order {
+    name: String,
+    hungry: bool,
+    food: Option<String>,
+}
+
+impl FromAnswers for Order {
+    fn from_answers(answers: &Answers) -> Result<Self> {
+        Ok(Order {
+            name: answers.value("name")?,
+            hungry: answers.value("hungry")?,
+            food: answers.optional("food")?,
+        })
+    }
+}
+
+#[test]
+fn collects_into_a_struct() {
+    let script = Script::new()
+        .text("Rasmus")
+        .enter()
+        .text("y")
+        .enter()
+        .enter();
+    let (mut form, _) = session(script);
+    let questions = Questions::new()
+        .question("name", Input::new("Name"))
+        .question("hungry", Confirm::new("Hungry?"))
+        .question("food", Select::new("Food", FOOD))
+        .when(|answers| answers.get_bool("hungry") == Some(true));
+
+    let order: Order = form.ask_as(&questions).unwrap();
+    assert_eq!(
+        order,
+        Order {
+            name: "Rasmus".to_string(),
+            hungry: true,
+            food: Some("Pizza".to_string()),
+        }
+    );
+}
+
+#[test]
+fn reports_mismatched_answers() {
+    let mut answers = Answers::new();
+    answers.insert("name", true);
+    let err = answers.value::<String>("name").unwrap_err();
+    assert!(matches!(err, Error::InvalidAnswer(name, _) if name == "name"));
+    let err = answers.value::<bool>("other").unwrap_err();
+    assert!(matches!(err, Error::NoAnswer(name) if name == "other"));
+}
+
+#[test]
+fn answers_typed_values() {
+    let sizes = [("Small", 25), ("Medium", 30), ("Large", 35)];
+    let script = Script::new()
+        .text("3")
+        .enter()
+        .text("42")
+        .enter()
+        .key(Key::Down)
+        .enter()
+        .key(Key::Down)
+        .text(" ")
+        .key(Key::Down)
+        .text(" ")
+        .enter();
+    let (mut form, _) = session(script);
+    let questions = Questions::new()
+        .question("count", Number::<u8>::new("How many?"))
+        .question("age", Input::new("Age").parse::<u32>())
+        .question("size", Select::new("Size", &sizes))
+        .question("extra", MultiSelect::new("Extra", &sizes));
+    let answers = form.ask(&questions).unwrap();
+
+    assert_eq!(answers.get("count"), Some(&Answer::Int(3)));
+    assert_eq!(answers.value::<u32>("age").unwrap(), 42);
+    assert_eq!(answers.value::<i32>("size").unwrap(), 30);
+    assert_eq!(answers.value::<Vec<u64>>("extra").unwrap(), vec![30, 35]);
+    assert_eq!(answers.value::<f64>("size").unwrap(), 30.0);
+}
@@ -0,0 +1,279 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Entry, Form, Select, SelectBuilder, Selection, Theme};
+use std::collections::HashMap;
+use termion::event::Key;
+
+fn choices() -> Vec<String> {
+    (1..=20).map(|i| format!("Choice {}", i)).collect()
+}
+
+#[test]
+fn enter_picks_first() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().enter());
+    let choice = form.run(Select::new("Pick", &choices).build()).unwrap();
+    assert_eq!(choice, "Choice 1");
+    assert_eq!(term.contents(), "? Pick Choice 1");
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn draws_a_page_of_rows() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().key(Key::Down));
+    form.run(Select::new("Pick", &choices).page_size(3).build())
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Choice 1");
+    assert_eq!(screen[2].trim(), "❯ Choice 2");
+    assert_eq!(screen[3].trim(), "Choice 3");
+    assert_eq!(screen[4], "");
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let choices = choices();
+    let script = Script::new().keys(vec![Key::Down; 5]);
+    let (mut form, term) = session(script);
+    form.run(Select::new("Pick", &choices).page_size(3).build())
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Choice 4");
+    assert_eq!(screen[3].trim(), "❯ Choice 6");
+
+    let script = Script::new()
+        .keys(vec![Key::Down; 5])
+        .keys(vec![Key::Up; 4])
+        .enter();
+    let (mut form, _) = session(script);
+    let choice = form
+        .run(Select::new("Pick", &choices).page_size(3).build())
+        .unwrap();
+    assert_eq!(choice, "Choice 2");
+}
+
+#[test]
+fn stops_at_the_last_choice() {
+    let choices = choices();
+    let script = Script::new().keys(vec![Key::Down; 30]).enter();
+    let (mut form, _) = session(script);
+    let choice = form
+        .run(Select::new("Pick", &choices).page_size(3).build())
+        .unwrap();
+    assert_eq!(choice, "Choice 20");
+}
+
+#[test]
+fn filters_as_you_type() {
+    let choices = choices();
+    let script = Script::new().text("c15").enter();
+    let (mut form, term) = session(script);
+    let choice = form
+        .run(Select::new("Pick", &choices).filter(true).build())
+        .unwrap();
+    assert_eq!(choice, "Choice 15");
+    assert_eq!(term.contents(), "? Pick Choice 15");
+}
+
+#[test]
+fn empty_choices_are_no_answer() {
+    let choices: Vec<String> = Vec::new();
+    let (mut form, term) = session(Script::new().enter());
+    let err = form.run(Select::new("Pick", &choices).build()).unwrap_err();
+    assert!(matches!(err, Error::NoAnswer(msg) if msg == "Pick"));
+    assert!(term.screen().iter().all(|line| line.is_empty()));
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().key(Key::Down).key(Key::Ctrl('c')));
+    let err = form.run(Select::new("Pick", &choices).build()).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+    assert!(!term.is_raw());
+}
+
+fn sizes() -> SelectBuilder<'static, String> {
+    Select::new(
+        "Size",
+        ["Small", "Medium", "Large"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[test]
+fn returns_references_to_borrowed_choices() {
+    let choices = choices();
+    let (mut form, _) = session(Script::new().key(Key::Down).enter());
+    let choice: &String = form
+        .run(Select::new("Pick", choices.iter().collect::<Vec<_>>()))
+        .unwrap();
+    assert!(std::ptr::eq(choice, &choices[1]));
+}
+
+#[test]
+fn owns_choices_from_an_iterator() {
+    let (mut form, term) = session(Script::new().key(Key::Down).enter());
+    let size: String = form.run(sizes()).unwrap();
+    assert_eq!(size, "Medium");
+    assert_eq!(term.contents(), "? Size Medium");
+
+    let mut output = Vec::new();
+    let size = Form::new("Large\n".as_bytes(), &mut output, Theme::new())
+        .run(sizes())
+        .unwrap();
+    assert_eq!(size, "Large");
+}
+
+#[test]
+fn starts_on_the_default() {
+    let choices = choices();
+    let (mut form, term) = session(Script::new().key(Key::Down));
+    form.run(Select::new("Pick", &choices).page_size(3).default(9))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Choice 9");
+    assert_eq!(screen[3].trim(), "❯ Choice 11");
+
+    let (mut form, _) = session(Script::new().enter());
+    let choice = form
+        .run(Select::new("Pick", &choices).default_by(|c| c.ends_with(" 2")))
+        .unwrap();
+    assert_eq!(choice, "Choice 2");
+
+    let (mut form, _) = session(Script::new().enter());
+    let choice = form.run(Select::new("Pick", &choices).default(99)).unwrap();
+    assert_eq!(choice, "Choice 1");
+}
+
+#[test]
+fn default_answers_when_not_interactive() {
+    let choices = choices();
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(Select::new("Pick", &choices).default(4))
+    };
+    assert_eq!(run("").unwrap(), "Choice 5");
+    assert_eq!(run("\n").unwrap(), "Choice 5");
+    assert_eq!(run("Choice 7\n").unwrap(), "Choice 7");
+}
+
+#[test]
+fn indexed_returns_index_and_value() {
+    let sizes = vec![("Small", 's'), ("Medium", 'm'), ("Large", 'l')];
+
+    let (mut form, term) = session(Script::new().key(Key::Down).enter());
+    let size = form.run(Select::new("Size", &sizes).indexed()).unwrap();
+    assert_eq!(
+        size,
+        Selection {
+            index: 1,
+            value: 'm'
+        }
+    );
+    assert_eq!(term.contents(), "? Size Medium");
+
+    let mut output = Vec::new();
+    let size = Form::new("Large\n".as_bytes(), &mut output, Theme::new())
+        .run(Select::new("Size", &sizes).indexed())
+        .unwrap();
+    assert_eq!(
+        size,
+        Selection {
+            index: 2,
+            value: 'l'
+        }
+    );
+
+    let mut answers = HashMap::new();
+    answers.insert("size", "Small");
+    let (form, _) = session(Script::new());
+    let size = form
+        .answers(answers)
+        .run(Select::new("Size", &sizes).name("size").indexed())
+        .unwrap();
+    assert_eq!(
+        size,
+        Selection {
+            index: 0,
+            value: 's'
+        }
+    );
+}
+
+fn menu() -> Vec<Entry<&'static str>> {
+    vec![
+        Entry::Separator("Fruit"),
+        Entry::Enabled("Apple"),
+        Entry::disabled("Banana", "sold out"),
+        Entry::Enabled("Cherry"),
+        Entry::Separator("Greens"),
+        Entry::Enabled("Leek"),
+    ]
+}
+
+#[test]
+fn skips_disabled_and_separators() {
+    let menu = menu();
+    let (mut form, term) = session(Script::new().key(Key::Down));
+    form.run(Select::new("Pick", &menu)).unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Fruit");
+    assert_eq!(screen[2].trim(), "Apple");
+    assert_eq!(screen[3].trim(), "Banana (sold out)");
+    assert_eq!(screen[4].trim(), "❯ Cherry");
+    assert_eq!(screen[5].trim(), "Greens");
+
+    let script = Script::new().keys(vec![Key::Down; 3]).enter();
+    let (mut form, _) = session(script);
+    assert_eq!(
+        *form.run(Select::new("Pick", &menu)).unwrap().choice(),
+        "Leek"
+    );
+
+    let script = Script::new().key(Key::Down).keys(vec![Key::Up; 2]).enter();
+    let (mut form, _) = session(script);
+    assert_eq!(
+        *form.run(Select::new("Pick", &menu)).unwrap().choice(),
+        "Apple"
+    );
+
+    let (mut form, _) = session(Script::new().enter());
+    let choice = form.run(Select::new("Pick", &menu).default(2)).unwrap();
+    assert_eq!(*choice.choice(), "Apple");
+}
+
+#[test]
+fn scrolls_headings_into_view() {
+    let menu = menu();
+    let script = Script::new().key(Key::Down).key(Key::Up).key(Key::Up);
+    let (mut form, term) = session(script);
+    form.run(Select::new("Pick", &menu).page_size(2))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "Fruit");
+    assert_eq!(screen[2].trim(), "❯ Apple");
+}
+
+#[test]
+fn rejects_disabled_answers() {
+    let menu = menu();
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        Form::new(input.as_bytes(), &mut output, Theme::new()).run(Select::new("Pick", &menu))
+    };
+    assert_eq!(*run("Cherry\n").unwrap().choice(), "Cherry");
+    assert!(matches!(
+        run("Banana\n"),
+        Err(Error::InvalidAnswer(_, msg)) if msg == "Banana is disabled: sold out"
+    ));
+    assert!(matches!(run("Fruit\n"), Err(Error::InvalidAnswer(..))));
+}
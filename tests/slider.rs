@@ -0,0 +1,128 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Form, Slider, Theme};
+use termion::event::Key;
+
+#[test]
+fn enter_picks_default() {
+    let (mut form, term) = session(Script::new().enter());
+    let n = form.run(Slider::new("Workers", 1, 32).default(8)).unwrap();
+    assert_eq!(n, 8);
+    assert_eq!(term.contents(), "? Workers 8");
+    assert!(term.is_cursor_visible());
+
+    let (mut form, _) = session(Script::new().enter());
+    assert_eq!(form.run(Slider::new("Workers", 1, 32)).unwrap(), 1);
+}
+
+#[test]
+fn draws_the_bar() {
+    let (mut form, term) = session(Script::new());
+    form.run(Slider::new("Volume", 0, 100).default(50).width(11))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[0], "? Volume 50");
+    assert_eq!(screen[1], "  ━━━━━●─────");
+
+    let (mut form, term) = session(Script::new());
+    form.run(
+        Slider::new("Volume", 0, 100)
+            .width(5)
+            .labels("quiet", "loud"),
+    )
+    .unwrap_err();
+    assert_eq!(term.screen()[1], "  quiet ●──── loud");
+}
+
+#[test]
+fn narrows_to_the_terminal() {
+    let (mut form, term) = session(Script::new());
+    form.run(Slider::new("Volume", 0, 100).default(100).width(80))
+        .unwrap_err();
+    let bar = term.screen()[1].clone();
+    assert_eq!(bar.chars().count(), 39);
+    assert!(bar.ends_with("━●"));
+}
+
+#[test]
+fn arrows_step_and_page_jumps() {
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Right, Key::Left, Key::PageUp])
+        .enter();
+    let (mut form, _) = session(script);
+    let n = form.run(Slider::new("Percent", 0, 100).step(5)).unwrap();
+    assert_eq!(n, 55);
+
+    let script = Script::new()
+        .key(Key::PageUp)
+        .key(Key::PageDown)
+        .key(Key::PageDown)
+        .enter();
+    let (mut form, _) = session(script);
+    let n = form
+        .run(Slider::new("Percent", 0, 100).default(50).big_step(25))
+        .unwrap();
+    assert_eq!(n, 25);
+}
+
+#[test]
+fn stays_within_bounds() {
+    let script = Script::new().keys(vec![Key::PageUp; 5]).enter();
+    let (mut form, _) = session(script);
+    assert_eq!(form.run(Slider::new("Workers", 1, 32)).unwrap(), 32);
+
+    let script = Script::new()
+        .key(Key::End)
+        .key(Key::Home)
+        .key(Key::Left)
+        .enter();
+    let (mut form, _) = session(script);
+    assert_eq!(form.run(Slider::new("Workers", 1, 32)).unwrap(), 1);
+
+    let script = Script::new().key(Key::Right).enter();
+    let (mut form, _) = session(script);
+    let n = form
+        .run(Slider::new("Ratio", 0.0, 1.0).step(0.25).default(0.9))
+        .unwrap();
+    assert_eq!(n, 1.0);
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(Slider::new("Workers", 1, 32)).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+    assert_eq!(term.screen()[1], "");
+}
+
+#[test]
+fn parses_answers_when_not_interactive() {
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        let result = Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(Slider::new("Workers", 1, 32).default(4));
+        (result, String::from_utf8(output).unwrap())
+    };
+
+    let (n, output) = run("16\n");
+    assert_eq!(n.unwrap(), 16);
+    assert_eq!(output, "? Workers 16\n");
+    assert_eq!(run("").0.unwrap(), 4);
+    assert!(matches!(
+        run("64\n").0,
+        Err(Error::InvalidAnswer(_, msg)) if msg == "Must be between 1 and 32"
+    ));
+    assert!(matches!(run("many\n").0, Err(Error::InvalidAnswer(..))));
+}
+
+#[test]
+fn rejects_nan() {
+    let mut output = Vec::new();
+    let result = Form::new("NaN\n".as_bytes(), &mut output, Theme::new())
+        .run(Slider::new("Ratio", 0.0, 1.0));
+    assert!(matches!(result, Err(Error::InvalidAnswer(..))));
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Form, Sort, Theme};
+use termion::event::Key;
+
+fn steps() -> Vec<&'static str> {
+    vec!["Database", "Cache", "Backend", "Frontend", "Docs"]
+}
+
+#[test]
+fn enter_keeps_order() {
+    let steps = steps();
+    let (mut form, term) = session(Script::new().enter());
+    let order = form.run(Sort::new("Deploy order", &steps)).unwrap();
+    assert_eq!(order, steps.iter().collect::<Vec<_>>());
+    assert!(term.screen()[0].starts_with("? Deploy order Database, Cache, "));
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn space_grabs_and_moves() {
+    let steps = steps();
+    let script = Script::new()
+        .key(Key::Down)
+        .text(" ")
+        .keys(vec![Key::Down, Key::Down])
+        .text(" ")
+        .key(Key::Up)
+        .text(" ")
+        .key(Key::Up)
+        .text(" ")
+        .enter();
+    let (mut form, _) = session(script);
+    let order = form.run(Sort::new("Deploy order", &steps)).unwrap();
+    assert_eq!(
+        order,
+        vec![&"Database", &"Frontend", &"Backend", &"Cache", &"Docs"]
+    );
+}
+
+#[test]
+fn draws_ranks() {
+    let steps = steps();
+    let script = Script::new().text(" ").key(Key::Down);
+    let (mut form, term) = session(script);
+    form.run(Sort::new("Deploy order", &steps)).unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "1. Cache");
+    assert_eq!(screen[2].trim(), "❯ 2. Database");
+    assert_eq!(screen[5].trim(), "5. Docs");
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let steps = steps();
+    let script = Script::new().text(" ").keys(vec![Key::Down; 10]);
+    let (mut form, term) = session(script);
+    form.run(Sort::new("Deploy order", &steps).page_size(3))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "3. Frontend");
+    assert_eq!(screen[2].trim(), "4. Docs");
+    assert_eq!(screen[3].trim(), "❯ 5. Database");
+    assert_eq!(screen[4], "");
+}
+
+#[test]
+fn zero_page_size_shows_one_row() {
+    let steps = steps();
+    let script = Script::new().text(" ").key(Key::Down).text(" ").enter();
+    let (mut form, _) = session(script);
+    let order = form
+        .run(Sort::new("Deploy order", &steps).page_size(0))
+        .unwrap();
+    assert_eq!(order[..2], [&"Cache", &"Database"]);
+}
+
+#[test]
+fn listed_choices_come_first_when_not_interactive() {
+    let steps = steps();
+    let sort = Sort::new("Deploy order", &steps).build();
+    let mut output = Vec::new();
+    let mut form = Form::new("Docs, 0\n".as_bytes(), &mut output, Theme::new());
+    let order = form.run(sort).unwrap();
+    assert_eq!(
+        order,
+        vec![&"Docs", &"Database", &"Cache", &"Backend", &"Frontend"]
+    );
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "? Deploy order Docs, Database, Cache, Backend, Frontend\n"
+    );
+}
+
+#[test]
+fn end_of_input_is_no_answer() {
+    let steps = steps();
+    let run = |input: &str| {
+        let mut output = Vec::new();
+        Form::new(input.as_bytes(), &mut output, Theme::new())
+            .run(Sort::new("Deploy order", &steps))
+    };
+    assert!(matches!(run(""), Err(Error::NoAnswer(msg)) if msg == "Deploy order"));
+    assert_eq!(run("\n").unwrap(), steps.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let steps = steps();
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('c')));
+    let err = form.run(Sort::new("Deploy order", &steps)).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn indexed_returns_the_new_order() {
+    let steps = steps();
+    let script = Script::new().text(" ").key(Key::Down).text(" ").enter();
+    let (mut form, _) = session(script);
+    let order = form
+        .run(Sort::new("Deploy order", &steps).indexed())
+        .unwrap();
+    assert_eq!(order, vec![1, 0, 2, 3, 4]);
+
+    let mut output = Vec::new();
+    let order = Form::new("Docs, Cache\n".as_bytes(), &mut output, Theme::new())
+        .run(Sort::new("Deploy order", &steps).indexed())
+        .unwrap();
+    assert_eq!(order, vec![4, 1, 0, 2, 3]);
+}
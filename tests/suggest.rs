@@ -0,0 +1,72 @@
+mod common;
+
+use common::session;
+use prompt::testing::Script;
+use prompt::{History, Input, WordCompleter};
+use termion::event::Key;
+
+fn history() -> History {
+    let history = History::new();
+    history.add("db.example.com").unwrap();
+    history.add("web.example.com").unwrap();
+    history
+}
+
+#[test]
+fn shows_suggestion_after_cursor() {
+    let history = history();
+    let (mut form, term) = session(Script::new().text("db"));
+    form.run(Input::new("Host").suggester(&history))
+        .unwrap_err();
+
+    assert_eq!(term.screen()[0], "? Host db.example.com");
+    assert_eq!(term.cursor(), (9, 0));
+}
+
+#[test]
+fn right_accepts_suggestion() {
+    let history = history();
+    let script = Script::new().text("we").key(Key::Right).text("/").enter();
+    let (mut form, _) = session(script);
+    let host = form.run(Input::new("Host").suggester(&history)).unwrap();
+
+    assert_eq!(host, "web.example.com/");
+}
+
+#[test]
+fn enter_ignores_suggestion() {
+    let history = history();
+    let (mut form, term) = session(Script::new().text("db").enter());
+    let host = form.run(Input::new("Host").suggester(&history)).unwrap();
+
+    assert_eq!(host, "db");
+    assert_eq!(term.contents(), "? Host db");
+}
+
+#[test]
+fn only_suggests_at_end_of_line() {
+    let words = WordCompleter::new(vec!["prompt"]);
+    let script = Script::new().text("pr").keys([Key::Left, Key::End]);
+    let (mut form, term) = session(script);
+    form.run(Input::new("Word").suggester(&words)).unwrap_err();
+
+    // Moving to the end shows the suggestion again without accepting it
+    assert_eq!(term.screen()[0], "? Word prompt");
+    assert_eq!(term.cursor(), (9, 0));
+}
+
+#[test]
+fn suggests_from_history_by_default() {
+    let history = history();
+    let (mut form, term) = session(Script::new().text("we").key(Key::End).enter());
+    let host = form.run(Input::new("Host").history(&history)).unwrap();
+
+    assert_eq!(host, "web.example.com");
+    assert_eq!(term.contents(), "? Host web.example.com");
+
+    let words = WordCompleter::new(vec!["webmail"]);
+    let (mut form, term) = session(Script::new().text("we"));
+    form.run(Input::new("Host").history(&history).suggester(&words))
+        .unwrap_err();
+    assert_eq!(term.screen()[0], "? Host webmail");
+}
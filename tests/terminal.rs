@@ -0,0 +1,11 @@
+use prompt::Terminal;
+use std::io::stdout;
+use std::time::{Duration, Instant};
+
+#[test]
+fn stdout_leaves_stdin_alone_until_read_through_stdin_wrap() {
+    // Keys read ahead here would never reach a reader of `std::io::stdin()`
+    let start = Instant::now();
+    assert!(!stdout().poll_input(Duration::from_secs(5)).unwrap());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
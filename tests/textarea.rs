@@ -0,0 +1,130 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Submit, TextArea};
+use termion::event::Key;
+
+#[test]
+fn enter_inserts_newlines() {
+    let script = Script::new()
+        .text("Subject")
+        .enter()
+        .enter()
+        .text("Body")
+        .key(Key::Ctrl('d'));
+    let (mut form, term) = session(script);
+    let text = form.run(TextArea::new("Message")).unwrap();
+
+    assert_eq!(text, "Subject\n\nBody");
+    assert_eq!(term.contents(), "? Message (3 lines)");
+}
+
+#[test]
+fn alt_enter_submits() {
+    let script = Script::new()
+        .text("one")
+        .enter()
+        .text("two")
+        .key(Key::Alt('\r'));
+    let (mut form, _) = session(script);
+    let text = form
+        .run(TextArea::new("Message").submit(Submit::AltEnter))
+        .unwrap();
+
+    assert_eq!(text, "one\ntwo");
+}
+
+#[test]
+fn soft_wraps_long_lines() {
+    let long = "a".repeat(45);
+    let script = Script::new().text(&long);
+    let (mut form, term) = session(script);
+    form.run(TextArea::new("Message")).unwrap_err();
+
+    let screen = term.screen();
+    assert_eq!(screen[1], "a".repeat(40));
+    assert_eq!(screen[2], "a".repeat(5));
+    assert_eq!(term.cursor(), (5, 2));
+}
+
+#[test]
+fn scrolls_to_the_cursor() {
+    let script = Script::new().text("1\n2\n3\n4\n5").key(Key::Up);
+    let (mut form, term) = session(script);
+    form.run(TextArea::new("Message").height(3)).unwrap_err();
+
+    let screen = term.screen();
+    assert_eq!(&screen[1..4], &["3", "4", "5"]);
+    assert_eq!(term.cursor(), (1, 2));
+}
+
+#[test]
+fn moves_between_lines() {
+    let script = Script::new()
+        .text("abc")
+        .enter()
+        .text("d")
+        .keys([Key::Up, Key::Right, Key::Right])
+        .text("X")
+        .keys([Key::Down, Key::Home])
+        .text("Y")
+        .key(Key::Ctrl('d'));
+    let (mut form, _) = session(script);
+    let text = form.run(TextArea::new("Message")).unwrap();
+
+    assert_eq!(text, "abcX\nYd");
+}
+
+#[test]
+fn validates_on_submit() {
+    let script = Script::new()
+        .key(Key::Ctrl('d'))
+        .text("fix")
+        .key(Key::Ctrl('d'));
+    let (mut form, term) = session(script);
+    let text = form
+        .run(TextArea::new("Message").validate(valid::MinLen(1)))
+        .unwrap();
+
+    assert_eq!(text, "fix");
+    assert_eq!(term.contents(), "? Message (1 line)");
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let (mut form, _) = session(Script::new().text("x").key(Key::Ctrl('c')));
+    let err = form.run(TextArea::new("Message")).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+}
+
+#[test]
+fn summarizes_empty_and_trailing_lines() {
+    let (mut form, term) = session(Script::new().key(Key::Ctrl('d')));
+    let text = form.run(TextArea::new("Message")).unwrap();
+    assert_eq!(text, "");
+    assert_eq!(term.contents(), "? Message (empty)");
+
+    let (mut form, term) = session(Script::new().text("one").enter().key(Key::Ctrl('d')));
+    let text = form.run(TextArea::new("Message")).unwrap();
+    assert_eq!(text, "one\n");
+    assert_eq!(term.contents(), "? Message (2 lines)");
+}
+
+#[test]
+fn reads_lines_up_to_a_dot_when_not_interactive() {
+    let mut out = Vec::new();
+    let input = &b"Subject\n\nBody\n.\nnext\n"[..];
+    let mut form = prompt::Form::new(input, &mut out, prompt::Theme::new());
+    let text = form.run(TextArea::new("Message")).unwrap();
+    let next = form.run(prompt::Input::new("Next")).unwrap();
+    assert_eq!(text, "Subject\n\nBody");
+    assert_eq!(next, "next");
+
+    let mut out = Vec::new();
+    let mut form = prompt::Form::new(&b"one\ntwo"[..], &mut out, prompt::Theme::new());
+    let text = form.run(TextArea::new("Message")).unwrap();
+    assert_eq!(text, "one\ntwo");
+    assert_eq!(String::from_utf8(out).unwrap(), "? Message (2 lines)\n");
+}
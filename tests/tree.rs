@@ -0,0 +1,233 @@
+mod common;
+
+use common::session;
+use prompt::error::Error;
+use prompt::testing::Script;
+use prompt::{Form, Theme, TreeSelect, TreeSource};
+use std::cell::Cell;
+use termion::event::Key;
+
+/// Teams, counting how often children are loaded
+#[derive(Default)]
+struct Org {
+    loads: Cell<usize>,
+}
+
+impl TreeSource for Org {
+    type Node = &'static str;
+
+    fn roots(&self) -> Vec<&'static str> {
+        vec!["Engineering", "Sales"]
+    }
+
+    fn children(&self, node: &&'static str) -> Vec<&'static str> {
+        self.loads.set(self.loads.get() + 1);
+        match *node {
+            "Engineering" => vec!["Backend", "Frontend"],
+            "Backend" => vec!["API", "DB"],
+            "Sales" => vec!["EMEA"],
+            _ => Vec::new(),
+        }
+    }
+
+    fn has_children(&self, node: &&'static str) -> bool {
+        matches!(*node, "Engineering" | "Backend" | "Sales")
+    }
+}
+
+#[test]
+fn picks_a_nested_node() {
+    let org = Org::default();
+    let script = Script::new()
+        .keys(vec![
+            Key::Right,
+            Key::Down,
+            Key::Right,
+            Key::Right,
+            Key::Down,
+        ])
+        .enter();
+    let (mut form, term) = session(script);
+    let team = form.run(TreeSelect::new("Team", &org)).unwrap();
+    assert_eq!(team, "DB");
+    assert_eq!(term.contents(), "? Team DB");
+    assert!(term.is_cursor_visible());
+}
+
+#[test]
+fn loads_children_when_expanded() {
+    let org = Org::default();
+    let (mut form, _) = session(Script::new().key(Key::Down).enter());
+    assert_eq!(form.run(TreeSelect::new("Team", &org)).unwrap(), "Sales");
+    assert_eq!(org.loads.get(), 0);
+
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Left, Key::Right])
+        .enter();
+    let (mut form, _) = session(script);
+    form.run(TreeSelect::new("Team", &org)).unwrap();
+    assert_eq!(org.loads.get(), 1);
+}
+
+#[test]
+fn draws_nested_nodes() {
+    let org = Org::default();
+    let script = Script::new().keys(vec![Key::Right, Key::Down, Key::Right]);
+    let (mut form, term) = session(script);
+    form.run(TreeSelect::new("Team", &org)).unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1], "    ▾ Engineering");
+    assert_eq!(screen[2], "  ❯   ▾ Backend");
+    assert_eq!(screen[3], "          API");
+    assert_eq!(screen[5], "        Frontend");
+    assert_eq!(screen[6], "    ▸ Sales");
+    assert_eq!(screen[7], "");
+}
+
+#[test]
+fn left_collapses_or_moves_to_parent() {
+    let org = Org::default();
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Down, Key::Left, Key::Left, Key::Down])
+        .enter();
+    let (mut form, term) = session(script);
+    assert_eq!(form.run(TreeSelect::new("Team", &org)).unwrap(), "Sales");
+    assert_eq!(term.screen()[1], "");
+}
+
+#[test]
+fn scrolls_past_the_page() {
+    let org = Org::default();
+    let script = Script::new().keys(vec![
+        Key::Right,
+        Key::Down,
+        Key::Right,
+        Key::Down,
+        Key::Down,
+        Key::Down,
+    ]);
+    let (mut form, term) = session(script);
+    form.run(TreeSelect::new("Team", &org).page_size(3))
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1].trim(), "API");
+    assert_eq!(screen[3].trim(), "❯     Frontend");
+    assert_eq!(screen[4], "");
+}
+
+#[test]
+fn parents_show_partial_selection() {
+    let org = Org::default();
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Down])
+        .text(" ")
+        .key(Key::Up);
+    let (mut form, term) = session(script);
+    form.run(TreeSelect::new("Teams", &org).multiple())
+        .unwrap_err();
+    let screen = term.screen();
+    assert_eq!(screen[1], "  ❯ ◐ ▾ Engineering");
+    assert_eq!(screen[2], "    ◉   ▸ Backend");
+    assert_eq!(screen[3], "    ◯     Frontend");
+
+    let script = Script::new()
+        .keys(vec![Key::Right, Key::Down])
+        .text(" ")
+        .enter();
+    let (mut form, term) = session(script);
+    let teams = form.run(TreeSelect::new("Teams", &org).multiple()).unwrap();
+    assert_eq!(teams, vec!["Backend"]);
+    assert_eq!(term.contents(), "? Teams Backend");
+}
+
+#[test]
+fn checking_a_parent_checks_everything_below() {
+    let org = Org::default();
+    let script = Script::new().text(" ");
+    let (mut form, _) = session(script);
+    form.run(TreeSelect::new("Teams", &org).multiple())
+        .unwrap_err();
+    assert_eq!(org.loads.get(), 0);
+
+    let script = Script::new().text(" ").key(Key::Right);
+    let (mut form, term) = session(script);
+    form.run(TreeSelect::new("Teams", &org).multiple())
+        .unwrap_err();
+    assert_eq!(term.screen()[1], "  ❯ ◉ ▾ Engineering");
+    assert_eq!(term.screen()[2], "    ◉   ▸ Backend");
+
+    let script = Script::new().text(" ").key(Key::Right).text("  ").enter();
+    let (mut form, _) = session(script);
+    let teams = form.run(TreeSelect::new("Teams", &org).multiple()).unwrap();
+    assert_eq!(teams, vec!["Engineering", "Backend", "Frontend"]);
+
+    let script = Script::new().text("  ").enter();
+    let (mut form, _) = session(script);
+    let teams = form.run(TreeSelect::new("Teams", &org).multiple()).unwrap();
+    assert_eq!(teams, Vec::<&str>::new());
+}
+
+#[test]
+fn ctrl_c_aborts() {
+    let org = Org::default();
+    let (mut form, term) = session(Script::new().key(Key::Right).key(Key::Ctrl('c')));
+    let err = form.run(TreeSelect::new("Team", &org)).unwrap_err();
+    assert!(matches!(err, Error::UserAborted));
+    assert!(term.is_cursor_visible());
+    assert!(!term.is_raw());
+}
+
+#[test]
+fn takes_paths_when_not_interactive() {
+    let org = Org::default();
+    let run = |input: &str, multiple: bool| {
+        let mut output = Vec::new();
+        let mut form = Form::new(input.as_bytes(), &mut output, Theme::new());
+        let result = if multiple {
+            form.run(TreeSelect::new("Team", &org).multiple())
+        } else {
+            form.run(TreeSelect::new("Team", &org))
+                .map(|team| vec![team])
+        };
+        (result, String::from_utf8(output).unwrap())
+    };
+
+    let (team, output) = run("Engineering / Backend / DB\n", false);
+    assert_eq!(team.unwrap(), vec!["DB"]);
+    assert_eq!(output, "? Team DB\n");
+    assert!(matches!(
+        run("Backend\n", false).0,
+        Err(Error::InvalidAnswer(..))
+    ));
+    assert!(matches!(run("", false).0, Err(Error::NoAnswer(..))));
+
+    assert!(matches!(run("\n", true).0, Err(Error::NoAnswer(..))));
+    assert!(matches!(run("", true).0, Err(Error::NoAnswer(..))));
+
+    let (teams, output) = run("Sales, Engineering/Frontend\n", true);
+    assert_eq!(teams.unwrap(), vec!["Frontend", "Sales", "EMEA"]);
+    assert_eq!(output, "? Team Frontend, Sales, EMEA\n");
+}
+
+/// An org with no teams at all
+struct Empty;
+
+impl TreeSource for Empty {
+    type Node = &'static str;
+
+    fn roots(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn children(&self, _: &&'static str) -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+#[test]
+fn empty_tree_is_no_answer() {
+    let (mut form, term) = session(Script::new().enter());
+    let err = form.run(TreeSelect::new("Team", &Empty)).unwrap_err();
+    assert!(matches!(err, Error::NoAnswer(msg) if msg == "Team"));
+    assert!(term.screen().iter().all(|line| line.is_empty()));
+}